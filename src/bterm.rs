@@ -10,12 +10,9 @@ use bracket_terminal::{
 
 use crate::{
     grid::{Cell, Color, Grid},
-    input::{Event, Input, Key},
+    input::{Command, Event, Input, Key},
     reality::Reality,
-    world::{
-        render::{CHARS_GRID, LINES_GRID},
-        World,
-    },
+    world::render::{CHARS_GRID, LINES_GRID},
 };
 
 struct BTermState {
@@ -24,10 +21,16 @@ struct BTermState {
 }
 
 impl BTermState {
-    pub fn new(world: World) -> Self {
+    pub fn new(simulation: Reality) -> Self {
         Self {
             pressed_keys: Default::default(),
-            simulation: Reality::new(world),
+            simulation,
+        }
+    }
+
+    fn save(&mut self) {
+        if let Err(error) = self.simulation.persist() {
+            log(format!("failed to save: {error}"));
         }
     }
 }
@@ -89,6 +92,31 @@ impl TryFrom<VirtualKeyCode> for Key {
             VirtualKeyCode::Key8 => Ok(Key::Number8),
             VirtualKeyCode::Key9 => Ok(Key::Number9),
 
+            VirtualKeyCode::Space => Ok(Key::Space),
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => Ok(Key::Enter),
+            VirtualKeyCode::Escape => Ok(Key::Escape),
+            VirtualKeyCode::Tab => Ok(Key::Tab),
+            VirtualKeyCode::Back => Ok(Key::Backspace),
+            VirtualKeyCode::Delete => Ok(Key::Delete),
+            VirtualKeyCode::Insert => Ok(Key::Insert),
+            VirtualKeyCode::Home => Ok(Key::Home),
+            VirtualKeyCode::End => Ok(Key::End),
+            VirtualKeyCode::PageUp => Ok(Key::PageUp),
+            VirtualKeyCode::PageDown => Ok(Key::PageDown),
+
+            VirtualKeyCode::F1 => Ok(Key::F1),
+            VirtualKeyCode::F2 => Ok(Key::F2),
+            VirtualKeyCode::F3 => Ok(Key::F3),
+            VirtualKeyCode::F4 => Ok(Key::F4),
+            VirtualKeyCode::F5 => Ok(Key::F5),
+            VirtualKeyCode::F6 => Ok(Key::F6),
+            VirtualKeyCode::F7 => Ok(Key::F7),
+            VirtualKeyCode::F8 => Ok(Key::F8),
+            VirtualKeyCode::F9 => Ok(Key::F9),
+            VirtualKeyCode::F10 => Ok(Key::F10),
+            VirtualKeyCode::F11 => Ok(Key::F11),
+            VirtualKeyCode::F12 => Ok(Key::F12),
+
             _ => {
                 log(format!("unrecognized key {key:?}"));
                 Err(())
@@ -144,7 +172,7 @@ fn receive_input(ctx: &mut BTerm, state: &mut BTermState) -> Input {
 
     let mut new_key = None;
 
-    while matches!(new_key, None) {
+    while new_key.is_none() {
         match input.pop() {
             Some(BEvent::KeyboardInput {
                 key: vkey,
@@ -163,18 +191,27 @@ fn receive_input(ctx: &mut BTerm, state: &mut BTermState) -> Input {
                     }
                 }
             }
-            Some(BEvent::CloseRequested) => ctx.quit(),
+            Some(BEvent::CloseRequested) => {
+                state.save();
+                ctx.quit()
+            }
             None => break,
             _ => {}
         }
     }
 
-    Input {
+    let input = Input {
         event: new_key.map(Event::Key),
         mouse_x: mouse_tile.x as usize,
         mouse_y: mouse_tile.y as usize,
-        fps: ctx.fps,
+    };
+
+    if state.simulation.command_for_input(&input) == Some(Command::Quit) {
+        state.save();
+        ctx.quit();
     }
+
+    input
 }
 
 impl GameState for BTermState {
@@ -187,7 +224,7 @@ impl GameState for BTermState {
     }
 }
 
-pub fn main(world: World) -> BError {
+pub fn main(simulation: Reality) -> BError {
     let context = BTermBuilder::new()
         .with_dimensions(CHARS_GRID, LINES_GRID)
         .with_tile_dimensions(10, 16)
@@ -197,6 +234,6 @@ pub fn main(world: World) -> BError {
         .with_advanced_input(true)
         .build()?;
 
-    let gs: BTermState = BTermState::new(world);
+    let gs: BTermState = BTermState::new(simulation);
     main_loop(context, gs)
 }
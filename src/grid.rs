@@ -1,4 +1,7 @@
-use std::ops::{Index, IndexMut, Range};
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut, Range},
+};
 
 use self::text::Text;
 
@@ -183,7 +186,7 @@ impl<T> Grid<T> {
         }
     }
 
-    pub fn view(&mut self) -> MutGridView<T> {
+    pub fn view(&mut self) -> MutGridView<'_, T> {
         let width = self.width;
         let height = self.height();
         MutGridView {
@@ -197,6 +200,16 @@ impl<T> Grid<T> {
     }
 }
 
+impl Display for Grid<Cell> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in self.data.chunks(self.width) {
+            let line: String = line.iter().map(|cell| cell.character).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
 pub struct MutGridView<'a, T> {
     reference: &'a mut Grid<T>,
     start_line_id: usize,
@@ -0,0 +1,176 @@
+use std::{error::Error, fs, path::Path};
+
+use crate::{
+    duration::{
+        Duration, Style, TICKS_PER_DAY, TICKS_PER_HOUR, TICKS_PER_MILLISECOND, TICKS_PER_MINUTE,
+        TICKS_PER_SECOND, TICKS_PER_YEAR,
+    },
+    grid::{Cell, Grid},
+    input::{Event, Input, Key},
+    key_bindings::KeyBindings,
+    world::World,
+};
+
+enum Command {
+    At(Duration),
+    Advance(Duration),
+    Press(Key),
+    Render,
+}
+
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let mut ticks = 0;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(|| format!("missing unit in duration {text}"))?;
+        let (number, after_number) = rest.split_at(digits);
+        let number: u128 = number
+            .parse()
+            .map_err(|_| format!("missing number in duration {text}"))?;
+
+        let unit_length = after_number
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(after_number.len());
+        let (unit, after_unit) = after_number.split_at(unit_length);
+        let unit = match unit {
+            "ms" => TICKS_PER_MILLISECOND,
+            "s" => TICKS_PER_SECOND,
+            "m" => TICKS_PER_MINUTE,
+            "h" => TICKS_PER_HOUR,
+            "d" => TICKS_PER_DAY,
+            "y" => TICKS_PER_YEAR,
+            _ => return Err(format!("unknown unit {unit} in duration {text}")),
+        };
+
+        ticks = number
+            .checked_mul(unit)
+            .and_then(|part| part.checked_add(ticks))
+            .ok_or_else(|| format!("duration {text} is too long"))?;
+        rest = after_unit;
+    }
+
+    Ok(ticks * Duration::TICK)
+}
+
+fn parse_line(line: &str) -> Result<Vec<Command>, String> {
+    let line = line.split('#').next().unwrap_or_default();
+    let words: Vec<_> = line.split_whitespace().collect();
+
+    match words.as_slice() {
+        [] => Ok(vec![]),
+        ["render"] => Ok(vec![Command::Render]),
+        ["press", key] => Ok(vec![Command::Press(key.parse()?)]),
+        ["advance", duration] => Ok(vec![Command::Advance(parse_duration(duration)?)]),
+        ["at", time] => Ok(vec![Command::At(parse_duration(time)?)]),
+        ["at", time, "press", key] => Ok(vec![
+            Command::At(parse_duration(time)?),
+            Command::Press(key.parse()?),
+        ]),
+        _ => Err(format!("cannot parse command '{}'", line.trim())),
+    }
+}
+
+/// One command per line, `#` starts a comment:
+///
+/// ```text
+/// at 3s press F    # wait until 3 seconds after the start, then press F
+/// advance 1h30m    # wait for 90 more minutes
+/// render           # print the grid
+/// ```
+fn parse_script(script: &str) -> Result<Vec<Command>, String> {
+    let mut commands = Vec::new();
+    for (line_id, line) in script.lines().enumerate() {
+        let line_commands =
+            parse_line(line).map_err(|error| format!("line {}: {error}", line_id + 1))?;
+        commands.extend(line_commands);
+    }
+    Ok(commands)
+}
+
+fn input(event: Option<Event>) -> Input {
+    Input {
+        event,
+        mouse_x: 0,
+        mouse_y: 0,
+    }
+}
+
+fn render(world: &World) -> Grid<Cell> {
+    world.render(&input(None))
+}
+
+pub fn main(
+    mut world: World,
+    key_bindings: &KeyBindings,
+    script_path: &Path,
+    print_grid: bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let commands = parse_script(&fs::read_to_string(script_path)?)?;
+    let start = world.get_total_ticks();
+
+    for command in commands {
+        match command {
+            Command::At(time) => {
+                let total_ticks = start
+                    .checked_add(time)
+                    .ok_or_else(|| "time is too far in the future".to_owned())?;
+                if total_ticks < world.get_total_ticks() {
+                    return Err(format!(
+                        "cannot go back in time to {}",
+                        time.stringify(Style::Compact)
+                    )
+                    .into());
+                }
+                world.simulate(total_ticks);
+            }
+            Command::Advance(delta) => {
+                let total_ticks = world
+                    .get_total_ticks()
+                    .checked_add(delta)
+                    .ok_or_else(|| "cannot advance that far into the future".to_owned())?;
+                world.simulate(total_ticks);
+            }
+            Command::Press(key) => {
+                if let Some(command) = key_bindings.command_for_input(&input(Some(Event::Key(key))))
+                {
+                    world.handle_command(command);
+                }
+            }
+            Command::Render => print!("{}", render(&world)),
+        }
+    }
+
+    print_result(&world, print_grid);
+    Ok(())
+}
+
+pub fn print_result(world: &World, print_grid: bool) {
+    for line in world.describe() {
+        println!("{}", line);
+    }
+
+    if print_grid {
+        print!("{}", render(world));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::duration::Duration;
+
+    use super::parse_duration;
+
+    #[test]
+    fn durations() {
+        assert_eq!(
+            parse_duration("1m30s"),
+            Ok(Duration::MINUTE + Duration::from_seconds(30))
+        );
+        assert_eq!(parse_duration("1y"), Ok(Duration::YEAR));
+        assert!(parse_duration("3").is_err());
+        assert!(parse_duration("3w").is_err());
+        assert!(parse_duration("999999999999999999999999y").is_err());
+    }
+}
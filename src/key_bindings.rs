@@ -0,0 +1,176 @@
+use std::{error::Error, fmt::Display, fs, io, path::Path};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::input::{Command, Event, Input, Key};
+
+pub const DEFAULT_KEY_BINDINGS_PATH: &str = "key_bindings.ron";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyBindings {
+    keys: IndexMap<Command, Vec<Key>>,
+}
+
+#[derive(Debug)]
+pub enum KeyBindingError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Conflict {
+        key: Key,
+        commands: (Command, Command),
+    },
+}
+
+impl Display for KeyBindingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyBindingError::Io(error) => write!(f, "cannot read key bindings: {error}"),
+            KeyBindingError::Parse(error) => write!(f, "invalid key bindings: {error}"),
+            KeyBindingError::Conflict { key, commands } => write!(
+                f,
+                "key {key:?} is bound to both {:?} and {:?}",
+                commands.0, commands.1
+            ),
+        }
+    }
+}
+
+impl Error for KeyBindingError {}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut keys: IndexMap<_, _> = [
+            (Command::Up, vec![Key::Up]),
+            (Command::Down, vec![Key::Down]),
+            (Command::Left, vec![Key::Left]),
+            (Command::Right, vec![Key::Right]),
+            (Command::PrintFlyer, vec![Key::F]),
+            (Command::HandoutFlyer, vec![Key::H]),
+            (Command::ManualResearch, vec![Key::R]),
+            (Command::ResearchTree, vec![Key::T]),
+            (Command::Pause, vec![Key::W]),
+            (Command::Start, vec![Key::S]),
+            (Command::MoveEarlier, vec![Key::U]),
+            (Command::MoveLater, vec![Key::D]),
+            (Command::Remove, vec![Key::X]),
+            (Command::EndCampaign, vec![Key::E]),
+            (Command::NewGame, vec![Key::N]),
+            // The window quits by closing it, only the terminal needs a key.
+            (Command::Quit, vec![]),
+        ]
+        .into();
+        for number in 0..10 {
+            keys.insert(Command::Choose(number), vec![Key::number(number)]);
+        }
+
+        Self { keys }
+    }
+}
+
+impl KeyBindings {
+    pub fn terminal_default() -> Self {
+        let mut bindings = Self::default();
+        bindings
+            .keys
+            .insert(Command::Quit, vec![Key::Q, Key::Escape]);
+        bindings
+    }
+
+    pub fn command(&self, key: Key) -> Option<Command> {
+        self.keys
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(&command, _)| command)
+    }
+
+    pub fn command_for_input(&self, input: &Input) -> Option<Command> {
+        match input.event {
+            Some(Event::Key(key)) => self.command(key),
+            None => None,
+        }
+    }
+
+    /// Applies the bindings of `config` on top of the frontend's `defaults`, a map from commands
+    /// to keys like `{ PrintFlyer: [P], Choose(1): [Number1, A] }`.
+    pub fn from_config(config: &str, defaults: Self) -> Result<Self, KeyBindingError> {
+        let overrides: IndexMap<Command, Vec<Key>> =
+            ron::from_str(config).map_err(KeyBindingError::Parse)?;
+
+        let mut bindings = defaults;
+        bindings.keys.extend(overrides);
+        bindings.validate()?;
+        Ok(bindings)
+    }
+
+    pub fn load(path: &Path, defaults: Self) -> Result<Self, KeyBindingError> {
+        Self::from_config(
+            &fs::read_to_string(path).map_err(KeyBindingError::Io)?,
+            defaults,
+        )
+    }
+
+    fn validate(&self) -> Result<(), KeyBindingError> {
+        let mut bound: IndexMap<Key, Command> = IndexMap::new();
+        for (&command, keys) in &self.keys {
+            for &key in keys {
+                if let Some(other) = bound.insert(key, command) {
+                    if other != command {
+                        return Err(KeyBindingError::Conflict {
+                            key,
+                            commands: (other, command),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{Command, Key};
+
+    use super::{KeyBindingError, KeyBindings};
+
+    #[test]
+    fn defaults_are_valid() {
+        KeyBindings::default().validate().unwrap();
+        KeyBindings::terminal_default().validate().unwrap();
+        assert_eq!(KeyBindings::default().command(Key::Q), None);
+        assert_eq!(
+            KeyBindings::terminal_default().command(Key::Q),
+            Some(Command::Quit)
+        );
+    }
+
+    #[test]
+    fn overrides() {
+        let bindings = KeyBindings::from_config(
+            "{ PrintFlyer: [P, Y, Space], Quit: [] }",
+            KeyBindings::terminal_default(),
+        )
+        .unwrap();
+
+        assert_eq!(bindings.command(Key::P), Some(Command::PrintFlyer));
+        assert_eq!(bindings.command(Key::Space), Some(Command::PrintFlyer));
+        assert_eq!(bindings.command(Key::F), None);
+        assert_eq!(bindings.command(Key::Q), None);
+        assert_eq!(bindings.command(Key::H), Some(Command::HandoutFlyer));
+    }
+
+    #[test]
+    fn conflicts() {
+        let error =
+            KeyBindings::from_config("{ PrintFlyer: [H] }", KeyBindings::default()).unwrap_err();
+
+        assert!(matches!(
+            error,
+            KeyBindingError::Conflict {
+                key: Key::H,
+                commands: (Command::PrintFlyer, Command::HandoutFlyer)
+            }
+        ));
+    }
+}
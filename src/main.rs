@@ -1,17 +1,29 @@
-use std::error::Error;
+use std::{error::Error, path::Path, time::SystemTime};
 
-use world::World;
+use duration::Duration;
+use key_bindings::{KeyBindings, DEFAULT_KEY_BINDINGS_PATH};
+
+use options::{Frontend, Options};
+use reality::Reality;
+use world::{ResearchTree, World};
 
 mod grid;
+mod options;
 
 mod bterm;
+mod headless;
+mod key_bindings;
+mod replay;
 mod tui;
 
 mod world;
 
 mod input {
+    use std::str::FromStr;
+
+    use serde::{Deserialize, Serialize};
 
-    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
     pub enum Key {
         Down,
         Up,
@@ -55,6 +67,31 @@ mod input {
         Number7,
         Number8,
         Number9,
+
+        Space,
+        Enter,
+        Escape,
+        Tab,
+        Backspace,
+        Delete,
+        Insert,
+        Home,
+        End,
+        PageUp,
+        PageDown,
+
+        F1,
+        F2,
+        F3,
+        F4,
+        F5,
+        F6,
+        F7,
+        F8,
+        F9,
+        F10,
+        F11,
+        F12,
     }
     impl Key {
         pub(crate) fn number(number: usize) -> Self {
@@ -72,17 +109,131 @@ mod input {
                 _ => panic!("{:?}", number),
             }
         }
+
+        const LETTERS: [Self; 26] = [
+            Self::A,
+            Self::B,
+            Self::C,
+            Self::D,
+            Self::E,
+            Self::F,
+            Self::G,
+            Self::H,
+            Self::I,
+            Self::J,
+            Self::K,
+            Self::L,
+            Self::M,
+            Self::N,
+            Self::O,
+            Self::P,
+            Self::Q,
+            Self::R,
+            Self::S,
+            Self::T,
+            Self::U,
+            Self::V,
+            Self::W,
+            Self::X,
+            Self::Y,
+            Self::Z,
+        ];
+
+        const FUNCTION_KEYS: [Self; 12] = [
+            Self::F1,
+            Self::F2,
+            Self::F3,
+            Self::F4,
+            Self::F5,
+            Self::F6,
+            Self::F7,
+            Self::F8,
+            Self::F9,
+            Self::F10,
+            Self::F11,
+            Self::F12,
+        ];
+
+        /// The function key `F<number>`, if there is one.
+        pub(crate) fn function(number: u8) -> Option<Self> {
+            Self::FUNCTION_KEYS
+                .get(usize::from(number).checked_sub(1)?)
+                .copied()
+        }
+    }
+
+    /// Parses key names as written by humans: `F`, `f`, `7`, `Up`, `Space`, `F5`, ...
+    impl FromStr for Key {
+        type Err = String;
+
+        fn from_str(name: &str) -> Result<Self, Self::Err> {
+            let mut chars = name.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                if c.is_ascii_alphabetic() {
+                    return Ok(Self::LETTERS[(c.to_ascii_uppercase() as u8 - b'A') as usize]);
+                }
+                if let Some(digit) = c.to_digit(10) {
+                    return Ok(Self::number(digit as usize));
+                }
+            }
+
+            match name.to_ascii_lowercase().as_str() {
+                "down" => Ok(Self::Down),
+                "up" => Ok(Self::Up),
+                "left" => Ok(Self::Left),
+                "right" => Ok(Self::Right),
+                "space" => Ok(Self::Space),
+                "enter" => Ok(Self::Enter),
+                "escape" => Ok(Self::Escape),
+                "tab" => Ok(Self::Tab),
+                "backspace" => Ok(Self::Backspace),
+                "delete" => Ok(Self::Delete),
+                "insert" => Ok(Self::Insert),
+                "home" => Ok(Self::Home),
+                "end" => Ok(Self::End),
+                "pageup" => Ok(Self::PageUp),
+                "pagedown" => Ok(Self::PageDown),
+                lower => lower
+                    .strip_prefix('f')
+                    .and_then(|number| number.parse().ok())
+                    .and_then(Self::function)
+                    .ok_or_else(|| format!("unknown key {name}")),
+            }
+        }
+    }
+
+    /// What a key is bound to. The world decides what a command means in its current state.
+    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+    pub enum Command {
+        Up,
+        Down,
+        Left,
+        Right,
+        Choose(usize),
+        PrintFlyer,
+        HandoutFlyer,
+        ManualResearch,
+        ResearchTree,
+        Pause,
+        Start,
+        MoveEarlier,
+        MoveLater,
+        Remove,
+        EndCampaign,
+        NewGame,
+        Quit,
     }
 
+    #[derive(Clone, Copy, Serialize, Deserialize)]
     pub enum Event {
         Key(Key),
     }
 
+    #[derive(Clone, Copy, Serialize, Deserialize)]
     pub struct Input {
         pub event: Option<Event>,
         pub mouse_x: usize,
         pub mouse_y: usize,
-        pub fps: f32,
     }
 }
 
@@ -91,6 +242,8 @@ mod duration {
     //TODO
     use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
+    use serde::{Deserialize, Serialize};
+
     use crate::world::{
         quantity::{Quantity, QuantityType},
         rate::Rate,
@@ -130,20 +283,22 @@ mod duration {
     pub const MICRO: u128 = 1_000 * MILLI;
     pub const NANO: u128 = 1_000 * MICRO;
 
-    #[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+    #[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default, Serialize, Deserialize)]
     pub struct Duration {
         ticks: u128,
     }
 
     impl SubAssign for Duration {
         fn sub_assign(&mut self, rhs: Self) {
-            self.ticks -= rhs.ticks
+            *self = self
+                .checked_sub(rhs)
+                .expect("durations cannot become negative");
         }
     }
 
     impl AddAssign for Duration {
         fn add_assign(&mut self, rhs: Self) {
-            self.ticks += rhs.ticks
+            *self = self.checked_add(rhs).expect("duration overflow");
         }
     }
 
@@ -176,7 +331,7 @@ mod duration {
 
     impl MulAssign<u128> for Duration {
         fn mul_assign(&mut self, rhs: u128) {
-            self.ticks *= rhs;
+            *self = self.checked_mul(rhs).expect("duration overflow");
         }
     }
 
@@ -185,6 +340,35 @@ mod duration {
             self.ticks
         }
 
+        /// The longest duration there is, far beyond any session.
+        pub const MAX: Self = Duration { ticks: u128::MAX };
+
+        pub fn checked_add(self, rhs: Self) -> Option<Self> {
+            Some(Self {
+                ticks: self.ticks.checked_add(rhs.ticks)?,
+            })
+        }
+
+        pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+            Some(Self {
+                ticks: self.ticks.checked_sub(rhs.ticks)?,
+            })
+        }
+
+        pub fn checked_mul(self, rhs: u128) -> Option<Self> {
+            Some(Self {
+                ticks: self.ticks.checked_mul(rhs)?,
+            })
+        }
+
+        pub fn saturating_add(self, rhs: Self) -> Self {
+            self.checked_add(rhs).unwrap_or(Self::MAX)
+        }
+
+        pub fn saturating_mul(self, rhs: u128) -> Self {
+            self.checked_mul(rhs).unwrap_or(Self::MAX)
+        }
+
         pub const SECOND: Self = Duration {
             ticks: TICKS_PER_SECOND,
         };
@@ -196,7 +380,7 @@ mod duration {
             ticks: TICKS_PER_MICROSECOND,
         };
 
-        // const TICK: Self = Duration { ticks: 1 };
+        pub const TICK: Self = Duration { ticks: 1 };
         pub const INSTANT: Self = Duration { ticks: 0 };
 
         pub const YEAR: Self = Duration {
@@ -213,17 +397,20 @@ mod duration {
             }
         }
 
+        /// How long `rate` takes to reach `quantity`, `None` if it never does or takes longer than
+        /// [`Self::MAX`].
         pub(crate) fn from_quantity_and_rate_approximation<Q: QuantityType>(
             quantity: Quantity<Q>,
             rate: Rate<Q>,
-        ) -> Self {
+        ) -> Option<Self> {
             let rate_per_second_as_f64 = rate.per(Duration::SECOND).as_f64();
             let quantity_f64 = quantity.as_f64();
 
-            Self {
-                ticks: (quantity_f64 / rate_per_second_as_f64 * TICKS_PER_SECOND as f64).ceil()
-                    as u128,
-            }
+            // A zero rate gives an infinite or undefined number of ticks.
+            let ticks = (quantity_f64 / rate_per_second_as_f64 * TICKS_PER_SECOND as f64).ceil();
+            (ticks.is_finite() && ticks < u128::MAX as f64).then_some(Self {
+                ticks: ticks as u128,
+            })
         }
 
         pub const fn from_seconds(seconds: u128) -> Duration {
@@ -232,54 +419,237 @@ mod duration {
             }
         }
 
-        pub fn stringify(&self, precision: usize) -> String {
-            let precision_factor = 10_u128.pow(precision as u32);
+        /// Rounded up in the smallest unit shown, so waiting times are never understated.
+        pub fn stringify(&self, style: Style) -> String {
+            let hundredths = self.ticks.div_ceil(TICKS_PER_SECOND / 100);
+            if hundredths < SECONDS_PER_MIN * 100 {
+                let seconds =
+                    trim_decimals(format!("{}.{:02}", hundredths / 100, hundredths % 100));
+                return style.unit(&seconds, hundredths == 100, SECOND.short, SECOND.long);
+            }
+
+            for (large, small) in &UNIT_PAIRS {
+                let smalls = self.ticks.div_ceil(small.ticks);
+                if smalls.saturating_mul(small.ticks) < large.next_ticks {
+                    let per_large = large.ticks / small.ticks;
+                    let (larges, smalls) = (smalls / per_large, smalls % per_large);
+                    let larges =
+                        style.unit(&larges.to_string(), larges == 1, large.short, large.long);
+                    if smalls == 0 {
+                        return larges;
+                    }
+                    let smalls =
+                        style.unit(&smalls.to_string(), smalls == 1, small.short, small.long);
+                    return format!("{larges} {smalls}");
+                }
+            }
+
+            let tenths = self.ticks.div_ceil(TICKS_PER_YEAR / 10);
+            let years = trim_decimals(format!("{}.{}", tenths / 10, tenths % 10));
+            style.unit(&years, tenths == 10, YEAR.short, YEAR.long)
+        }
+    }
+
+    /// How a duration is written.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Style {
+        /// Like "2h 14m", for the small space on cards.
+        Compact,
+        /// Like "2 hours 14 minutes", for messages.
+        Verbose,
+    }
+
+    impl Style {
+        fn unit(self, value: &str, is_one: bool, short: &str, long: &str) -> String {
+            match self {
+                Style::Compact => format!("{value}{short}"),
+                Style::Verbose if is_one => format!("{value} {long}"),
+                Style::Verbose => format!("{value} {long}s"),
+            }
+        }
+    }
+
+    struct Unit {
+        ticks: u128,
+        /// Where the next larger unit takes over.
+        next_ticks: u128,
+        short: &'static str,
+        long: &'static str,
+    }
+
+    const SECOND: Unit = Unit {
+        ticks: TICKS_PER_SECOND,
+        next_ticks: TICKS_PER_MINUTE,
+        short: "s",
+        long: "second",
+    };
+    const MINUTE: Unit = Unit {
+        ticks: TICKS_PER_MINUTE,
+        next_ticks: TICKS_PER_HOUR,
+        short: "m",
+        long: "minute",
+    };
+    const HOUR: Unit = Unit {
+        ticks: TICKS_PER_HOUR,
+        next_ticks: TICKS_PER_DAY,
+        short: "h",
+        long: "hour",
+    };
+    const DAY: Unit = Unit {
+        ticks: TICKS_PER_DAY,
+        next_ticks: TICKS_PER_YEAR,
+        short: "d",
+        long: "day",
+    };
+    const YEAR: Unit = Unit {
+        ticks: TICKS_PER_YEAR,
+        next_ticks: u128::MAX,
+        short: "y",
+        long: "year",
+    };
+
+    /// Durations from a minute to a year are written in a unit and the next smaller one.
+    const UNIT_PAIRS: [(Unit, Unit); 3] = [(MINUTE, SECOND), (HOUR, MINUTE), (DAY, HOUR)];
+
+    fn trim_decimals(number: String) -> String {
+        number
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_owned()
+    }
+
+    /// Shown for estimates of things that do not progress.
+    pub const NEVER: &str = "never";
+
+    /// Writes how long something takes, or [`NEVER`] if it does not progress.
+    pub fn stringify_estimate(estimate: Option<Duration>, style: Style) -> String {
+        estimate.map_or_else(|| NEVER.to_owned(), |duration| duration.stringify(style))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::world::{
+            quantity::{types::ResearchPoints, Quantity},
+            rate::Rate,
+        };
+
+        use super::{stringify_estimate, Duration, Style, TICKS_PER_SECOND};
+
+        fn seconds(seconds: u128) -> Duration {
+            Duration::from_seconds(seconds)
+        }
+
+        #[test]
+        fn durations_pick_fitting_units() {
+            let compact = |duration: Duration| duration.stringify(Style::Compact);
+            assert_eq!(compact(Duration::INSTANT), "0s");
+            assert_eq!(compact(Duration::TICK), "0.01s");
+            assert_eq!(compact(seconds(45)), "45s");
+            assert_eq!(compact(seconds(2 * 3600 + 14 * 60)), "2h 14m");
+            assert_eq!(compact(seconds(3 * 86400 + 4 * 3600)), "3d 4h");
+            assert_eq!(compact(seconds(60 * 60)), "1h");
+            assert_eq!(compact(Duration::YEAR + 73 * seconds(86400)), "1.2y");
+        }
+
+        #[test]
+        fn durations_round_up() {
+            let almost_an_hour = Duration {
+                ticks: 3600 * TICKS_PER_SECOND - 1,
+            };
+            assert_eq!(almost_an_hour.stringify(Style::Compact), "1h");
+            assert_eq!(
+                (seconds(61) + Duration::TICK).stringify(Style::Compact),
+                "1m 2s"
+            );
+        }
 
-            let (base, base_str) =
-            //  if &Self::MINUTE <= self {
-            //     (Self::SECOND, "s")
-            // } else {
-                (Self::SECOND, "s")
-            // }
-            ;
+        #[test]
+        fn verbose_durations_spell_out_units() {
+            let verbose = |duration: Duration| duration.stringify(Style::Verbose);
+            assert_eq!(verbose(seconds(1)), "1 second");
+            assert_eq!(verbose(seconds(3600 + 60)), "1 hour 1 minute");
+            assert_eq!(verbose(seconds(3 * 86400 + 4 * 3600)), "3 days 4 hours");
+            assert_eq!(verbose(Duration::YEAR), "1 year");
+        }
 
-            let divisor_ticks = base.ticks / precision_factor;
-            assert_ne!(divisor_ticks, 0);
+        #[test]
+        fn zero_rates_never_finish() {
+            let cost: Quantity<ResearchPoints> = Quantity::new(10);
+            let estimate = Duration::from_quantity_and_rate_approximation(cost, Rate::default());
+            assert_eq!(stringify_estimate(estimate, Style::Compact), "never");
 
-            let value_rounded_up = (self.ticks + divisor_ticks - 1) / divisor_ticks;
+            let rate = Rate::new(Quantity::new(1), Duration::MINUTE);
+            let estimate = Duration::from_quantity_and_rate_approximation(cost, rate);
+            assert_eq!(stringify_estimate(estimate, Style::Compact), "10m");
+        }
 
-            format!(
-                "{:.*}{}",
-                precision,
-                value_rounded_up as f64 / precision_factor as f64,
-                base_str
-            )
+        #[test]
+        fn estimates_beyond_the_longest_duration_never_finish() {
+            let rate: Rate<ResearchPoints> = Rate::new(Quantity::new(1), Duration::YEAR);
+            let estimate = Duration::from_quantity_and_rate_approximation(Quantity::MAX, rate);
+            assert_eq!(estimate, None);
         }
     }
 }
 
 mod reality {
-    use std::time::Instant;
+    use std::{path::PathBuf, time::Instant};
 
     use crate::{
         duration::Duration,
         grid::{Cell, Grid},
-        input::Input,
-        world::World,
+        input::{Command, Input},
+        key_bindings::KeyBindings,
+        replay::ReplayRecorder,
+        world::{save::SaveError, World},
     };
 
     pub struct Reality {
         simulation: World,
         simulation_start_time: Option<Instant>,
         ticks_at_simulation_start: Duration,
+        key_bindings: KeyBindings,
+        save_path: PathBuf,
+        recording: Option<ReplayRecorder>,
     }
     impl Reality {
-        pub fn new(world: World) -> Self {
+        pub fn new(world: World, key_bindings: KeyBindings, save_path: PathBuf) -> Self {
             Self {
                 ticks_at_simulation_start: world.get_total_ticks(),
                 simulation: world,
                 simulation_start_time: None,
+                key_bindings,
+                save_path,
+                recording: None,
+            }
+        }
+
+        /// Records every update from now on into `replay_path`, written while playing.
+        pub fn record_to(&mut self, replay_path: PathBuf) -> Result<(), SaveError> {
+            self.recording = Some(ReplayRecorder::create(
+                &replay_path,
+                &self.simulation,
+                &self.key_bindings,
+            )?);
+            Ok(())
+        }
+
+        #[cfg(test)]
+        pub fn world(&self) -> &World {
+            &self.simulation
+        }
+
+        pub fn command_for_input(&self, input: &Input) -> Option<Command> {
+            self.key_bindings.command_for_input(input)
+        }
+
+        /// Saves the world, and flushes the replay if recording.
+        pub fn persist(&mut self) -> Result<(), SaveError> {
+            self.simulation.save(&self.save_path)?;
+            if let Some(recording) = &mut self.recording {
+                recording.flush()?;
             }
+            Ok(())
         }
 
         pub fn update(&mut self, input: &Input) -> Grid<Cell> {
@@ -295,20 +665,79 @@ mod reality {
 
             let total_ticks = ticks_since_simulation_start + self.ticks_at_simulation_start;
 
+            if let Some(recording) = &mut self.recording {
+                recording.record(total_ticks, input);
+            }
+
             self.simulation.simulate(total_ticks);
+            if let Some(command) = self.command_for_input(input) {
+                self.simulation.handle_command(command);
+            }
             self.simulation.render(input)
         }
     }
 }
 
 fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
-    let world = World::new();
+    let options = Options::from_args()?;
+    let default_key_bindings = match options.frontend {
+        Frontend::Terminal => KeyBindings::terminal_default(),
+        _ => KeyBindings::default(),
+    };
+    let key_bindings = match &options.key_bindings_path {
+        Some(path) => KeyBindings::load(path, default_key_bindings)?,
+        None if Path::new(DEFAULT_KEY_BINDINGS_PATH).exists() => {
+            KeyBindings::load(Path::new(DEFAULT_KEY_BINDINGS_PATH), default_key_bindings)?
+        }
+        None => default_key_bindings,
+    };
 
-    let use_terminal = std::env::args().nth(1) == Some("--terminal".into());
+    let research_tree = match &options.research_tree_path {
+        Some(path) => Some(ResearchTree::load(path)?),
+        None => None,
+    };
+    let new_world = || {
+        let mut world = research_tree.map_or_else(World::new, World::with_research_tree);
+        world.set_notation(options.notation);
+        world
+    };
+
+    match &options.frontend {
+        Frontend::Headless { script } => {
+            // Scripted runs always start from the prolog, so they are reproducible.
+            return headless::main(new_world(), &key_bindings, script, options.print_grid);
+        }
+        Frontend::Replay { replay } => {
+            return replay::main(replay, options.notation, options.print_grid)
+        }
+        Frontend::Window | Frontend::Terminal => {}
+    }
 
-    if use_terminal {
-        tui::main(world)
+    let world = if options.save_path.exists() {
+        let loaded = World::load(&options.save_path)?;
+        let mut world = loaded.world;
+        world.set_notation(options.notation);
+
+        // A clock that went backwards since the save counts as no time away.
+        let elapsed = loaded
+            .saved_at
+            .and_then(|saved_at| SystemTime::now().duration_since(saved_at).ok())
+            .map_or(Duration::INSTANT, Duration::from_time_duration_rounded_down);
+        world.catch_up(elapsed, options.maximal_offline_duration);
+
+        world
     } else {
-        bterm::main(world)
+        new_world()
+    };
+
+    let mut reality = Reality::new(world, key_bindings, options.save_path);
+    if let Some(record_path) = options.record_path {
+        reality.record_to(record_path)?;
+    }
+
+    match options.frontend {
+        Frontend::Window => bterm::main(reality),
+        Frontend::Terminal => tui::main(reality),
+        Frontend::Headless { .. } | Frontend::Replay { .. } => unreachable!(),
     }
 }
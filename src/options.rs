@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+
+use crate::{
+    duration::Duration,
+    world::{offline::DEFAULT_MAXIMAL_OFFLINE_DURATION, quantity::format::Notation},
+};
+
+pub const DEFAULT_SAVE_PATH: &str = "save_the_planet.ron";
+
+pub enum Frontend {
+    Window,
+    Terminal,
+    Headless { script: PathBuf },
+    Replay { replay: PathBuf },
+}
+
+pub struct Options {
+    pub frontend: Frontend,
+    /// Whether the headless and replay frontends print the final grid.
+    pub print_grid: bool,
+    pub record_path: Option<PathBuf>,
+    pub key_bindings_path: Option<PathBuf>,
+    /// Research tree used for new games, the builtin one if not given.
+    pub research_tree_path: Option<PathBuf>,
+    pub save_path: PathBuf,
+    pub maximal_offline_duration: Duration,
+    /// How quantities are written, scaled to units unless asked otherwise.
+    pub notation: Notation,
+}
+
+impl Options {
+    pub fn from_args() -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self {
+            frontend: Frontend::Window,
+            print_grid: false,
+            record_path: None,
+            key_bindings_path: None,
+            research_tree_path: None,
+            save_path: DEFAULT_SAVE_PATH.into(),
+            maximal_offline_duration: DEFAULT_MAXIMAL_OFFLINE_DURATION,
+            notation: Notation::default(),
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--terminal" => options.frontend = Frontend::Terminal,
+                "--headless" => {
+                    options.frontend = Frontend::Headless {
+                        script: args
+                            .next()
+                            .ok_or_else(|| "--headless expects a script path".to_owned())?
+                            .into(),
+                    }
+                }
+                "--replay" => {
+                    options.frontend = Frontend::Replay {
+                        replay: args
+                            .next()
+                            .ok_or_else(|| "--replay expects a path".to_owned())?
+                            .into(),
+                    }
+                }
+                "--record" => {
+                    options.record_path = Some(
+                        args.next()
+                            .ok_or_else(|| "--record expects a path".to_owned())?
+                            .into(),
+                    )
+                }
+                "--keys" => {
+                    options.key_bindings_path = Some(
+                        args.next()
+                            .ok_or_else(|| "--keys expects a path".to_owned())?
+                            .into(),
+                    )
+                }
+                "--research" => {
+                    options.research_tree_path = Some(
+                        args.next()
+                            .ok_or_else(|| "--research expects a path".to_owned())?
+                            .into(),
+                    )
+                }
+                "--print-grid" => options.print_grid = true,
+                "--save" => {
+                    options.save_path = args
+                        .next()
+                        .ok_or_else(|| "--save expects a path".to_owned())?
+                        .into()
+                }
+                "--offline-cap" => {
+                    let seconds = args
+                        .next()
+                        .ok_or_else(|| "--offline-cap expects a number of seconds".to_owned())?;
+                    let seconds = seconds
+                        .parse()
+                        .map_err(|_| format!("--offline-cap: {seconds} is not a number"))?;
+                    options.maximal_offline_duration = Duration::SECOND
+                        .checked_mul(seconds)
+                        .ok_or_else(|| format!("--offline-cap: {seconds}s is too long"))?;
+                }
+                "--notation" => {
+                    let name = args.next().ok_or_else(|| {
+                        "--notation expects units, engineering or scientific".to_owned()
+                    })?;
+                    options.notation = Notation::parse(&name)
+                        .ok_or_else(|| format!("--notation: unknown notation {name}"))?;
+                }
+                _ => return Err(format!("unknown argument {arg}")),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::duration::Duration;
+
+    use super::Options;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    #[test]
+    fn offline_cap_is_checked() {
+        let options = parse(&["--offline-cap", "60"]).unwrap();
+        assert_eq!(options.maximal_offline_duration, Duration::MINUTE);
+        assert!(parse(&["--offline-cap", &u128::MAX.to_string()]).is_err());
+    }
+}
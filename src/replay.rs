@@ -0,0 +1,237 @@
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    duration::Duration,
+    headless::print_result,
+    input::Input,
+    key_bindings::KeyBindings,
+    world::{
+        quantity::format::Notation,
+        save::{SaveError, SAVE_FORMAT_VERSION},
+        World,
+    },
+};
+
+/// Version of the replay format. Bump whenever the layout of the replay itself changes,
+/// changes of the embedded [`World`] are covered by [`SAVE_FORMAT_VERSION`].
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// Frames written between flushes, about a second of play. A crash loses at most these.
+const FRAMES_PER_FLUSH: usize = 60;
+
+/// The first line of a replay, alone so it can be read whatever follows.
+#[derive(Serialize, Deserialize)]
+struct ReplayHeader {
+    version: u32,
+    /// Replays are played on exactly the world they were recorded with, so unlike saves, older
+    /// world layouts are not supported.
+    save_version: u32,
+}
+
+/// The second line of a replay, followed by one [`Frame`] per line.
+#[derive(Serialize, Deserialize)]
+struct ReplayStart {
+    initial_world: World,
+    /// The bindings used while recording, as the recorded keys mean nothing without them.
+    key_bindings: KeyBindings,
+}
+
+/// One update. Every update is recorded, not only those with key presses, as splitting a
+/// duration into different steps can change the outcome.
+#[derive(Serialize, Deserialize)]
+struct Frame(Duration, Input);
+
+pub struct Replay {
+    start: ReplayStart,
+    frames: Vec<Frame>,
+}
+
+/// Writes a replay while it is recorded, so it survives a crash and never piles up in memory.
+pub struct ReplayRecorder {
+    file: BufWriter<File>,
+    unflushed_frames: usize,
+    /// The first failed write, recording stops there and [`Self::flush`] reports it.
+    error: Option<io::Error>,
+}
+
+impl ReplayRecorder {
+    pub fn create(
+        path: &Path,
+        initial_world: &World,
+        key_bindings: &KeyBindings,
+    ) -> Result<Self, SaveError> {
+        let mut file = BufWriter::new(File::create(path)?);
+        let header = ReplayHeader {
+            version: REPLAY_FORMAT_VERSION,
+            save_version: SAVE_FORMAT_VERSION,
+        };
+        writeln!(file, "{}", ron::to_string(&header)?)?;
+        let start = ReplayStartRef {
+            initial_world,
+            key_bindings,
+        };
+        writeln!(file, "{}", ron::to_string(&start)?)?;
+        file.flush()?;
+        Ok(Self {
+            file,
+            unflushed_frames: 0,
+            error: None,
+        })
+    }
+
+    pub fn record(&mut self, total_ticks: Duration, input: &Input) {
+        if self.error.is_some() {
+            return;
+        }
+        let frame = ron::to_string(&Frame(total_ticks, *input)).expect("frames always serialize");
+        let mut result = writeln!(self.file, "{frame}");
+        self.unflushed_frames += 1;
+        if FRAMES_PER_FLUSH <= self.unflushed_frames {
+            result = result.and_then(|()| self.file.flush());
+            self.unflushed_frames = 0;
+        }
+        self.error = result.err();
+    }
+
+    pub fn flush(&mut self) -> Result<(), SaveError> {
+        if let Some(error) = self.error.take() {
+            return Err(error.into());
+        }
+        self.unflushed_frames = 0;
+        Ok(self.file.flush()?)
+    }
+}
+
+#[derive(Serialize)]
+struct ReplayStartRef<'a> {
+    initial_world: &'a World,
+    key_bindings: &'a KeyBindings,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, SaveError> {
+        let replay = fs::read_to_string(path)?;
+        let mut lines = replay.lines();
+
+        let header: ReplayHeader = ron::from_str(lines.next().unwrap_or_default())?;
+        if header.version != REPLAY_FORMAT_VERSION || header.save_version != SAVE_FORMAT_VERSION {
+            return Err(SaveError::UnsupportedReplay {
+                version: header.version,
+                save_version: header.save_version,
+            });
+        }
+
+        let start = ron::from_str(lines.next().unwrap_or_default())?;
+        let lines: Vec<_> = lines.collect();
+        let mut frames = Vec::with_capacity(lines.len());
+        for (index, line) in lines.iter().enumerate() {
+            match ron::from_str(line) {
+                Ok(frame) => frames.push(frame),
+                // A crash may cut the last frame short.
+                Err(_) if index + 1 == lines.len() => {}
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(Self { start, frames })
+    }
+
+    /// Feeds all recorded updates into the initial world, the same way [`Reality`](crate::reality::Reality) did.
+    pub fn play(self) -> World {
+        let ReplayStart {
+            mut initial_world,
+            key_bindings,
+        } = self.start;
+        for Frame(total_ticks, input) in self.frames {
+            initial_world.simulate(total_ticks);
+            if let Some(command) = key_bindings.command_for_input(&input) {
+                initial_world.handle_command(command);
+            }
+        }
+        initial_world
+    }
+}
+
+pub fn main(
+    replay_path: &Path,
+    notation: Notation,
+    print_grid: bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut replay = Replay::load(replay_path)?;
+    replay.start.initial_world.set_notation(notation);
+    let world = replay.play();
+    print_result(&world, print_grid);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        thread::sleep,
+        time::{self, SystemTime},
+    };
+
+    use crate::{
+        input::{Event, Input, Key},
+        key_bindings::KeyBindings,
+        reality::Reality,
+        world::{save::SaveError, World},
+    };
+
+    use super::{Replay, REPLAY_FORMAT_VERSION};
+
+    #[test]
+    fn play_matches_live_session() {
+        let directory = std::env::temp_dir().join(format!("replay-test-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let replay_path = directory.join("session.replay");
+
+        let mut reality = Reality::new(
+            World::new(),
+            KeyBindings::default(),
+            directory.join("save.ron"),
+        );
+        reality.record_to(replay_path.clone()).unwrap();
+        for frame in 0..40 {
+            let key = if frame < 10 { Key::H } else { Key::F };
+            let input = Input {
+                event: (frame % 2 == 0 || frame < 10).then_some(Event::Key(key)),
+                mouse_x: 0,
+                mouse_y: 0,
+            };
+            reality.update(&input);
+            sleep(time::Duration::from_millis(1));
+        }
+        reality.persist().unwrap();
+
+        let now = SystemTime::now();
+        let replayed = Replay::load(&replay_path).unwrap().play();
+        assert_eq!(
+            replayed.to_save_string(now).unwrap(),
+            reality.world().to_save_string(now).unwrap()
+        );
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn replays_of_other_world_layouts_are_rejected() {
+        let path = std::env::temp_dir().join(format!("old-replay-{}.replay", std::process::id()));
+        let header = format!("(version: {REPLAY_FORMAT_VERSION}, save_version: 2)\n");
+        fs::write(&path, header).unwrap();
+        assert!(matches!(
+            Replay::load(&path),
+            Err(SaveError::UnsupportedReplay {
+                save_version: 2,
+                ..
+            })
+        ));
+        fs::remove_file(path).unwrap();
+    }
+}
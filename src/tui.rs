@@ -7,7 +7,7 @@ use ratatui::{
 
 use crate::{
     grid::{Cell, Grid},
-    world::World,
+    reality::Reality,
 };
 
 use self::{
@@ -98,9 +98,9 @@ pub mod tui_impl;
 /// Event handler.
 pub mod handler;
 
-pub fn main(world: World) -> AppResult<()> {
+pub fn main(reality: Reality) -> AppResult<()> {
     // Create an application.
-    let mut app = TuiState::new(world);
+    let mut app = TuiState::new(reality);
 
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(io::stderr());
@@ -121,5 +121,7 @@ pub fn main(world: World) -> AppResult<()> {
 
     // Exit the user interface.
     tui.exit()?;
+
+    app.simulation.persist()?;
     Ok(())
 }
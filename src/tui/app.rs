@@ -1,6 +1,6 @@
-use std::{collections::HashSet, error};
+use std::error;
 
-use crate::{input::Key, reality::Reality, world::World};
+use crate::reality::Reality;
 
 /// Application result type.
 pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error + Send + Sync>>;
@@ -8,21 +8,16 @@ pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error + Send + Syn
 pub struct TuiState {
     /// Is the application running?
     pub running: bool,
-    /// counter
-    pub counter: u8,
 
-    pub pressed_keys: HashSet<Key>,
     pub simulation: Reality,
 }
 
 impl TuiState {
     /// Constructs a new instance of [`App`].
-    pub fn new(world: World) -> Self {
+    pub fn new(reality: Reality) -> Self {
         Self {
             running: true,
-            counter: 0,
-            pressed_keys: Default::default(),
-            simulation: Reality::new(world),
+            simulation: reality,
         }
     }
 
@@ -33,16 +28,4 @@ impl TuiState {
     pub fn quit(&mut self) {
         self.running = false;
     }
-
-    pub fn increment_counter(&mut self) {
-        if let Some(res) = self.counter.checked_add(1) {
-            self.counter = res;
-        }
-    }
-
-    pub fn decrement_counter(&mut self) {
-        if let Some(res) = self.counter.checked_sub(1) {
-            self.counter = res;
-        }
-    }
 }
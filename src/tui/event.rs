@@ -7,6 +7,7 @@ use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use super::app::AppResult;
 
 /// Terminal events.
+#[allow(dead_code)]
 #[derive(Clone, Copy, Debug)]
 pub enum Event {
     /// Terminal tick.
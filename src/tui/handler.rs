@@ -1,7 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::prelude::Backend;
 
-use crate::input::{Event as MyEvent, Input, Key};
+use crate::input::{Command, Event as MyEvent, Input, Key};
 
 use super::{
     app::{AppResult, TuiState},
@@ -82,6 +82,19 @@ impl TryFrom<KeyCode> for Key {
             KeyCode::Char('8') => Ok(Key::Number8),
             KeyCode::Char('9') => Ok(Key::Number9),
 
+            KeyCode::Char(' ') => Ok(Key::Space),
+            KeyCode::Enter => Ok(Key::Enter),
+            KeyCode::Esc => Ok(Key::Escape),
+            KeyCode::Tab => Ok(Key::Tab),
+            KeyCode::Backspace => Ok(Key::Backspace),
+            KeyCode::Delete => Ok(Key::Delete),
+            KeyCode::Insert => Ok(Key::Insert),
+            KeyCode::Home => Ok(Key::Home),
+            KeyCode::End => Ok(Key::End),
+            KeyCode::PageUp => Ok(Key::PageUp),
+            KeyCode::PageDown => Ok(Key::PageDown),
+            KeyCode::F(number) => Key::function(number).ok_or(()),
+
             _ => {
                 //log(format!("unrecognized key {key:?}"));
                 Err(())
@@ -104,40 +117,76 @@ pub fn receive_input<B: Backend>(ctx: &Tui<B>, state: &mut TuiState) -> AppResul
         event: None,
         mouse_x: 0,
         mouse_y: 0,
-        fps: 0.0,
     })
 }
 
 /// Handles the key events and updates the state of [`App`].
 pub fn handle_key_events(key_event: KeyEvent, app: &mut TuiState) -> AppResult<Input> {
-    match key_event.code {
-        // Exit application on `ESC` or `q`
-        KeyCode::Esc | KeyCode::Char('q') => {
-            app.quit();
-        }
-        // Exit application on `Ctrl-C`
-        KeyCode::Char('c') | KeyCode::Char('C') => {
-            if key_event.modifiers == KeyModifiers::CONTROL {
-                app.quit();
-            }
-        }
-        // Counter handlers
-        KeyCode::Right => {
-            app.increment_counter();
-        }
-        KeyCode::Left => {
-            app.decrement_counter();
-        }
-        // Other handlers you could add here.
-        _ => {}
+    // Exit application on `Ctrl-C`, whatever the bindings are
+    if matches!(key_event.code, KeyCode::Char('c') | KeyCode::Char('C'))
+        && key_event.modifiers == KeyModifiers::CONTROL
+    {
+        app.quit();
     }
 
     let event = Key::try_from(key_event.code).ok().map(MyEvent::Key);
 
-    Ok(Input {
+    let input = Input {
         event,
         mouse_x: 0,
         mouse_y: 0,
-        fps: 0.0,
-    })
+    };
+
+    // Exit application on whatever is bound to quit, `q` and escape by default
+    if app.simulation.command_for_input(&input) == Some(Command::Quit) {
+        app.quit();
+    }
+
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{
+        input::{Event, Key},
+        key_bindings::KeyBindings,
+        reality::Reality,
+        tui::app::TuiState,
+        world::World,
+    };
+
+    use super::handle_key_events;
+
+    fn state(key_bindings: KeyBindings) -> TuiState {
+        let save_path = std::env::temp_dir().join("unused-save.ron");
+        TuiState::new(Reality::new(World::new(), key_bindings, save_path))
+    }
+
+    fn press(code: KeyCode, modifiers: KeyModifiers, app: &mut TuiState) -> Option<Event> {
+        handle_key_events(KeyEvent::new(code, modifiers), app)
+            .unwrap()
+            .event
+    }
+
+    #[test]
+    fn only_bound_keys_quit() {
+        let mut app = state(KeyBindings::terminal_default());
+        press(KeyCode::Esc, KeyModifiers::NONE, &mut app);
+        assert!(!app.running);
+
+        let rebound = KeyBindings::from_config(
+            "{ NewGame: [Escape], Quit: [Q] }",
+            KeyBindings::terminal_default(),
+        )
+        .unwrap();
+        let mut app = state(rebound);
+        let event = press(KeyCode::Esc, KeyModifiers::NONE, &mut app);
+        assert!(matches!(event, Some(Event::Key(Key::Escape))));
+        assert!(app.running);
+
+        press(KeyCode::Char('c'), KeyModifiers::CONTROL, &mut app);
+        assert!(!app.running);
+    }
 }
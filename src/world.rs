@@ -1,7 +1,16 @@
-use crate::duration::Duration;
+use serde::{Deserialize, Serialize};
 
-use self::{cards::Cards, message::Messages, quantity::Quantity};
+use crate::duration::{Duration, Style};
 
+use self::{
+    cards::Cards,
+    message::Messages,
+    quantity::{format::Notation, Quantity},
+};
+
+pub use self::cards::research::ResearchTree;
+
+pub mod action;
 pub mod quantity;
 pub mod rate;
 
@@ -9,10 +18,17 @@ mod cards;
 
 mod message;
 
+pub mod offline;
+pub mod save;
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct World {
     cards: Cards,
     total_ticks: Duration,
     messages: Messages,
+    /// How quantities are written, a choice of the player that is not saved.
+    #[serde(skip)]
+    notation: Notation,
 }
 
 impl World {
@@ -21,19 +37,41 @@ impl World {
     }
 
     pub fn new() -> Self {
+        Self::with_research_tree(ResearchTree::builtin())
+    }
+
+    pub fn with_research_tree(research_tree: ResearchTree) -> Self {
         Self {
-            cards: Cards::new(),
+            cards: Cards::new(research_tree),
             messages: Messages::new(),
             total_ticks: Duration::INSTANT,
+            notation: Notation::default(),
         }
     }
 
+    pub fn set_notation(&mut self, notation: Notation) {
+        self.notation = notation;
+    }
+
+    /// Human readable summary of the state, for output without a grid.
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Time: {}",
+            self.total_ticks.stringify(Style::Compact)
+        )];
+        lines.extend(self.cards.describe(self.notation));
+        lines
+    }
+
     pub fn simulate(&mut self, total_ticks: Duration) {
         assert!(self.total_ticks <= total_ticks);
         let delta = total_ticks - self.total_ticks;
 
         self.total_ticks += delta;
-        self.simulate_cards(delta);
+        // The world stands still once the climate is decided.
+        if self.climate_outcome().is_none() {
+            self.simulate_cards(delta);
+        }
         self.messages.simulate(delta);
     }
 }
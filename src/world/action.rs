@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+use crate::input::Command;
+
+use super::{
+    cards::{
+        activism::production::Machine,
+        campaign::Bonus,
+        research::{Project, ResearchPage},
+        staff::Role,
+        Card,
+    },
+    World,
+};
+
+/// Everything a player can do to the world.
+/// Frontends translate commands into actions, bots and tests can apply them directly.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    SelectCard(Card),
+    PrintFlyer,
+    HandoutFlyer,
+    ShowResearchPage(ResearchPage),
+    /// Shows the details of a project in the research tree.
+    HighlightResearch(Project),
+    /// Starts an available or queued project, pausing the active one.
+    StartResearch(Project),
+    /// Stops the active project without losing progress, the next queued one takes over.
+    PauseResearch,
+    /// Plans a project to be researched after the active one.
+    QueueResearch(Project),
+    SelectQueuedResearch(usize),
+    /// Moves a queued project to the given position in the queue.
+    MoveQueuedResearch(Project, usize),
+    DequeueResearch(Project),
+    ManualResearch,
+    /// Turns a willing supporter into a staff member with the given role.
+    Recruit(Role),
+    /// Moves a staff member from the other role to the given one.
+    Reassign(Role),
+    /// Adds a machine that produces flyers over time.
+    Build(Machine),
+    /// Buys a storage room for more flyers.
+    ExpandFlyerStorage,
+    /// Spends inspiration on a bonus for this and all later campaigns.
+    BuyBonus(Bonus),
+    /// Converts the achievements into inspiration and starts over.
+    EndCampaign,
+    /// Replaces a world whose climate is decided with a new one.
+    NewGame,
+    /// Hands out flyers in the region with this index from now on.
+    TargetRegion(usize),
+}
+
+impl World {
+    /// What `command` means in the current state, if anything.
+    pub fn action_for_command(&self, command: Command) -> Option<Action> {
+        let action = match command {
+            Command::NewGame => Some(Action::NewGame),
+            _ if self.climate_outcome().is_some() => None,
+            Command::Up | Command::Down if !self.card_uses_arrow_keys() => {
+                self.menu_action_for_command(command)
+            }
+            _ => self.card_action_for_command(command),
+        }?;
+
+        self.is_possible(action).then_some(action)
+    }
+
+    fn menu_action_for_command(&self, command: Command) -> Option<Action> {
+        let available_cards = self.cards.available_cards();
+        if available_cards.len() < 2 {
+            return None;
+        }
+
+        let current_pos = available_cards
+            .iter()
+            .position(|c| c == &self.cards.selected)
+            .unwrap();
+        let new_pos = match command {
+            Command::Down => (available_cards.len() - 1).min(current_pos + 1),
+            Command::Up => current_pos.saturating_sub(1),
+            _ => unreachable!(),
+        };
+
+        Some(Action::SelectCard(available_cards[new_pos]))
+    }
+
+    /// After the end of the game, only a new one can be started.
+    pub fn is_possible(&self, action: Action) -> bool {
+        if self.climate_outcome().is_some() && action != Action::NewGame {
+            return false;
+        }
+
+        match action {
+            Action::SelectCard(card) => self.cards.available_cards().contains(&card),
+            Action::PrintFlyer => self.can_print_flyer_manually(),
+            Action::HandoutFlyer => self.can_handout_flyer_manually(),
+            Action::ShowResearchPage(page) => self.can_show_research_page(page),
+            Action::HighlightResearch(project) => self.can_highlight_research(project),
+            Action::StartResearch(project) => self.can_start_research(project),
+            Action::PauseResearch => self.can_pause_research(),
+            Action::QueueResearch(project) => self.can_queue_research(project),
+            Action::SelectQueuedResearch(index) => self.can_select_queued_research(index),
+            Action::MoveQueuedResearch(project, to) => self.can_move_queued_research(project, to),
+            Action::DequeueResearch(project) => self.can_dequeue_research(project),
+            Action::ManualResearch => self.can_research_manually(),
+            Action::Recruit(_) => self.can_recruit(),
+            Action::Reassign(role) => self.can_reassign(role),
+            Action::Build(machine) => self.can_build(machine),
+            Action::ExpandFlyerStorage => self.can_expand_flyer_storage(),
+            Action::BuyBonus(bonus) => self.can_buy_bonus(bonus),
+            Action::EndCampaign => self.can_end_campaign(),
+            Action::NewGame => self.can_start_new_game(),
+            Action::TargetRegion(region) => self.can_target_region(region),
+        }
+    }
+
+    /// Returns whether the action was possible. Impossible actions change nothing.
+    pub fn apply_action(&mut self, action: Action) -> bool {
+        if !self.is_possible(action) {
+            return false;
+        }
+
+        match action {
+            Action::SelectCard(card) => self.cards.selected = card,
+            Action::PrintFlyer => self.print_flyer_manually(),
+            Action::HandoutFlyer => self.handout_flyer_manually(),
+            Action::ShowResearchPage(page) => self.show_research_page(page),
+            Action::HighlightResearch(project) => self.highlight_research(project),
+            Action::StartResearch(project) => self.start_research(project),
+            Action::PauseResearch => self.pause_research(),
+            Action::QueueResearch(project) => self.queue_research(project),
+            Action::SelectQueuedResearch(index) => self.select_queued_research(index),
+            Action::MoveQueuedResearch(project, to) => self.move_queued_research(project, to),
+            Action::DequeueResearch(project) => self.dequeue_research(project),
+            Action::ManualResearch => self.research_manually(),
+            Action::Recruit(role) => self.recruit(role),
+            Action::Reassign(role) => self.reassign(role),
+            Action::Build(machine) => self.build(machine),
+            Action::ExpandFlyerStorage => self.expand_flyer_storage(),
+            Action::BuyBonus(bonus) => self.buy_bonus(bonus),
+            Action::EndCampaign => self.end_campaign(),
+            Action::NewGame => self.start_new_game(),
+            Action::TargetRegion(region) => self.target_region(region),
+        }
+
+        true
+    }
+
+    pub fn handle_command(&mut self, command: Command) {
+        if let Some(action) = self.action_for_command(command) {
+            self.apply_action(action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{duration::Duration, world::World};
+
+    use super::Action;
+
+    #[test]
+    fn prolog_without_keys() {
+        let mut world = World::new();
+        assert!(!world.apply_action(Action::PrintFlyer));
+
+        for _ in 0..10 {
+            assert!(world.apply_action(Action::HandoutFlyer));
+        }
+        assert!(!world.apply_action(Action::HandoutFlyer));
+
+        for _ in 0..6 {
+            assert!(world.apply_action(Action::PrintFlyer));
+        }
+        assert!(world.apply_action(Action::HandoutFlyer));
+    }
+
+    #[test]
+    fn printing_early_in_the_prolog_ends_it() {
+        let mut world = World::new();
+        for _ in 0..10 {
+            assert!(world.apply_action(Action::HandoutFlyer));
+        }
+        world.simulate(Duration::from_seconds(2 * 60 * 60));
+
+        assert!(world.apply_action(Action::PrintFlyer));
+        assert!(world.apply_action(Action::HandoutFlyer));
+    }
+}
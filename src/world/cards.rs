@@ -1,40 +1,85 @@
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+
 use crate::{
     duration::Duration,
     grid::{Cell, MutGridView},
-    input::Input,
+    input::Command,
     world::render::{CHARS_CARD, LINES_MAIN_FRAME_CONTENT},
 };
 
 use self::{
-    abstract_card::AbstractCard, activism::Activism, milestones::Milestones, research::Research,
+    abstract_card::AbstractCard,
+    activism::Activism,
+    campaign::Campaign,
+    climate::Climate,
+    funding::Funding,
+    map::WorldMap,
+    milestones::Milestones,
+    research::{Project, ProjectDefinition, Research, ResearchTree},
     staff::Staff,
 };
 
-use super::{quantity::Quantity, World};
+use super::{
+    action::Action,
+    quantity::{
+        format::Notation,
+        types::{Emission, Person},
+        Quantity,
+    },
+    World,
+};
 
-mod activism;
+pub mod activism;
+pub mod campaign;
+pub mod climate;
+pub mod funding;
+mod map;
 mod milestones;
-mod research;
-mod staff;
+pub mod research;
+pub mod staff;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum Card {
     CO2,
+    Climate,
+    Map,
+    Funding,
     Milestones,
     Research,
     Staff,
+    Campaign,
 }
 
-const ALL_CARDS: [Card; 4] = [Card::CO2, Card::Milestones, Card::Research, Card::Staff];
+/// Funding comes before the cards whose staff and projects it pays for.
+const ALL_CARDS: [Card; 8] = [
+    Card::CO2,
+    Card::Funding,
+    Card::Climate,
+    Card::Map,
+    Card::Milestones,
+    Card::Research,
+    Card::Staff,
+    Card::Campaign,
+];
 
 mod abstract_card;
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Cards {
     pub selected: Card,
     activism: Activism,
     milestones: Milestones,
     research: Research,
     staff: Staff,
+    #[serde(default = "Campaign::new")]
+    campaign: Campaign,
+    #[serde(default = "Climate::new")]
+    climate: Climate,
+    #[serde(default = "WorldMap::new")]
+    map: WorldMap,
+    #[serde(default = "Funding::new")]
+    funding: Funding,
 }
 
 impl World {
@@ -48,49 +93,149 @@ impl World {
         for card in ALL_CARDS {
             match card {
                 Card::CO2 => self.simulate_card_activism(delta),
+                Card::Climate => self.simulate_card_climate(delta),
+                Card::Map => self.simulate_card_map(delta),
+                Card::Funding => self.simulate_card_funding(delta),
                 Card::Milestones => self.simulate_card_milestones(delta),
                 Card::Research => self.simulate_card_research(delta),
                 Card::Staff => self.simulate_card_staff(delta),
+                Card::Campaign => self.simulate_card_campaign(delta),
             }
         }
     }
 
-    pub fn render_card(&mut self, input: &Input, view: MutGridView<'_, Cell>) {
+    pub fn render_card(&self, view: MutGridView<'_, Cell>) {
         assert_eq!(view.height(), LINES_MAIN_FRAME_CONTENT);
         assert!(CHARS_CARD <= view.width());
         match self.cards.selected {
-            Card::CO2 => self.render_card_activism(input, view),
-            Card::Milestones => self.render_card_milestones(input, view),
-            Card::Research => self.render_card_research(input, view),
-            Card::Staff => self.render_card_staff(input, view),
+            Card::CO2 => self.render_card_activism(view),
+            Card::Climate => self.render_card_climate(view),
+            Card::Map => self.render_card_map(view),
+            Card::Funding => self.render_card_funding(view),
+            Card::Milestones => self.render_card_milestones(view),
+            Card::Research => self.render_card_research(view),
+            Card::Staff => self.render_card_staff(view),
+            Card::Campaign => self.render_card_campaign(view),
+        }
+    }
+
+    /// Whether up and down belong to the card instead of the menu.
+    pub fn card_uses_arrow_keys(&self) -> bool {
+        match self.cards.selected {
+            Card::Research => self.research_uses_arrow_keys(),
+            Card::CO2
+            | Card::Climate
+            | Card::Map
+            | Card::Funding
+            | Card::Milestones
+            | Card::Staff
+            | Card::Campaign => false,
+        }
+    }
+
+    pub fn card_action_for_command(&self, command: Command) -> Option<Action> {
+        match self.cards.selected {
+            Card::CO2 => self.activism_action_for_command(command),
+            Card::Research => self.research_action_for_command(command),
+            Card::Staff => self.staff_action_for_command(command),
+            Card::Map => self.map_action_for_command(command),
+            Card::Campaign => self.campaign_action_for_command(command),
+            Card::Climate | Card::Funding | Card::Milestones => None,
         }
     }
 }
 
 impl Cards {
-    pub fn new() -> Cards {
+    pub fn new(research_tree: ResearchTree) -> Cards {
         Self {
             selected: Card::CO2,
             activism: Activism::new(),
             milestones: Milestones::new(),
             staff: Staff::new(),
-            research: Research::new(),
+            research: Research::new(research_tree),
+            campaign: Campaign::new(),
+            climate: Climate::new(),
+            map: WorldMap::new(),
+            funding: Funding::new(),
         }
     }
 
     pub fn available_cards(&self) -> Vec<Card> {
-        [Card::CO2, Card::Research, Card::Milestones, Card::Staff]
-            .into_iter()
-            .filter(|card| self.get_card(*card).is_visible())
-            .collect()
+        [
+            Card::CO2,
+            Card::Climate,
+            Card::Map,
+            Card::Funding,
+            Card::Research,
+            Card::Milestones,
+            Card::Staff,
+            Card::Campaign,
+        ]
+        .into_iter()
+        .filter(|card| self.get_card(*card).is_visible())
+        .collect()
+    }
+
+    /// Total emissions saved so far, not accounting for what was spent on flyers.
+    pub fn saved_emission(&self) -> Quantity<Emission> {
+        *self.activism.emission_balance.pos()
+    }
+
+    pub fn finished_research(&self) -> &IndexSet<Project> {
+        self.research.manager.finished()
+    }
+
+    pub fn research_definition(&self, project: Project) -> &ProjectDefinition {
+        self.research.manager.definition(project)
+    }
+
+    pub fn researchers(&self) -> Quantity<Person> {
+        self.staff.researcher
+    }
+
+    pub fn describe(&self, notation: Notation) -> Vec<String> {
+        let finished_research: Vec<_> = self
+            .finished_research()
+            .iter()
+            .map(|&project| self.research.manager.definition(project).name.clone())
+            .collect();
+
+        vec![
+            format!(
+                "Saved CO2e: {}",
+                self.activism
+                    .emission_balance
+                    .balance()
+                    .stringify(2, notation)
+            ),
+            format!("Flyer: {}", self.activism.flyer.stringify(0, notation)),
+            format!(
+                "Supporters: {}",
+                self.activism
+                    .population
+                    .supporting_people()
+                    .stringify(0, notation)
+            ),
+            format!(
+                "Researchers: {}",
+                self.staff.researcher.stringify(0, notation)
+            ),
+            format!("Money: {}", self.funding.money.stringify(2, notation)),
+            format!("Warming: +{:.2}°C", self.climate.projected_warming()),
+            format!("Finished research: {}", finished_research.join(", ")),
+        ]
     }
 
     pub fn get_card(&self, card: Card) -> &dyn AbstractCard {
         match card {
             Card::CO2 => &self.activism,
+            Card::Climate => &self.climate,
+            Card::Map => &self.map,
+            Card::Funding => &self.funding,
             Card::Milestones => &self.milestones,
             Card::Research => &self.research,
             Card::Staff => &self.staff,
+            Card::Campaign => &self.campaign,
         }
     }
 }
@@ -1,20 +1,35 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
     duration::Duration,
     grid::{Cell, Color, MutGridView},
-    input::Input,
+    input::Command,
     world::{
+        action::Action,
         message::{Message, STANDARD_MESSAGE_DURATION},
         quantity::{
             balance::Balance,
+            signed::SignedQuantity,
             types::{Emission, Flyer, Person},
             Quantity,
         },
-        rate::Rate,
+        rate::{Rate, RatePerUnit, SignedRate},
         World,
     },
 };
 
-use super::{abstract_card::AbstractCard, research::FLYER_EFFECTIVENESS_0};
+use self::{
+    opinion::Opinion,
+    production::{Machine, Production},
+    regions::{Population, REGIONS},
+};
+use super::{
+    abstract_card::AbstractCard, funding::FLYER_PRINT_PRICE, research::FLYER_EFFECTIVENESS_0,
+};
+
+pub mod opinion;
+pub mod production;
+pub mod regions;
 
 impl AbstractCard for Activism {
     fn menu_string(&self) -> String {
@@ -30,6 +45,7 @@ impl AbstractCard for Activism {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub enum Stage {
     Prolog { step: usize },
     Main,
@@ -49,10 +65,9 @@ impl Stage {
 }
 
 mod main {
-    use crate::world::cards::research::Project::Recycling;
+    use crate::world::cards::research::Trigger;
     use crate::{
         grid::{Cell, MutGridView},
-        input::{Event, Input, Key},
         world::{
             message::{Message, STANDARD_MESSAGE_DURATION},
             quantity::Quantity,
@@ -60,38 +75,48 @@ mod main {
         },
     };
 
+    use super::FLYER_PRINT_PRICE;
+
     impl World {
-        pub(super) fn render_activism_main(
-            &mut self,
-            input: &Input,
-            mut view: MutGridView<'_, Cell>,
-        ) {
-            match input.event {
-                Some(Event::Key(Key::F)) => {
-                    let success = self.manually_create_flyer();
-                    if !success {
-                        self.cards.research.manual_research_per_click = Quantity::fraction(1, 120);
-                        self.cards.research.manager.unlock(Recycling);
-                        self.messages.queue(Message::new(
-                            "Manuel research unlocked.".to_owned(),
-                            STANDARD_MESSAGE_DURATION,
-                        ));
-                    }
-                }
-                Some(Event::Key(Key::H)) => {
-                    self.handout_flyer();
-                }
-                _ => {}
+        pub(super) fn print_flyer_main(&mut self) {
+            if !self.has_room_for_one_more_flyer() {
+                self.messages.queue(Message::new(
+                    "The flyer storage is full.".to_owned(),
+                    STANDARD_MESSAGE_DURATION,
+                ));
+                return;
+            }
+            if !self.cards.activism.has_recycling && !self.can_pay_money(FLYER_PRINT_PRICE) {
+                self.messages.queue(Message::new(
+                    "Not enough money to print.".to_owned(),
+                    STANDARD_MESSAGE_DURATION,
+                ));
+                return;
             }
 
-            let activism = &mut self.cards.activism;
+            let success = self.manually_create_flyer();
+            if !success {
+                self.cards.research.manual_research_per_click = Quantity::fraction(1, 120);
+                self.cards.research.manager.trigger(Trigger::ManualResearch);
+                self.messages.queue(Message::new(
+                    "Manuel research unlocked.".to_owned(),
+                    STANDARD_MESSAGE_DURATION,
+                ));
+            }
+        }
+
+        pub(super) fn render_activism_main(&self, mut view: MutGridView<'_, Cell>) {
+            let activism = &self.cards.activism;
 
             view.print(
                 0,
                 0,
                 format!(
                     "Saved CO2e: {}",
-                    activism.emission_balance.balance().stringify(2)
+                    activism
+                        .emission_balance
+                        .balance()
+                        .stringify(2, self.notation)
                 )
                 .into(),
             );
@@ -99,34 +124,52 @@ mod main {
             view.print(
                 1,
                 0,
-                format!("Flyer: {}", activism.flyer.stringify(0)).into(),
+                format!(
+                    "Flyer: {} / {}",
+                    activism.flyer.stringify(0, self.notation),
+                    self.flyer_capacity().stringify(0, self.notation)
+                )
+                .into(),
             );
             view.print(
                 2,
                 0,
-                format!(" Rate: {}", activism.save_rate_from_flyers.stringify(4)).into(),
+                format!(
+                    " Rate: {}",
+                    self.net_emission_rate().stringify(4, self.notation)
+                )
+                .into(),
             );
             view.print(
                 3,
                 0,
                 format!(
                     " Supp: {} / {}",
-                    activism.supporting_people.stringify(0),
-                    &(activism.supporting_people + activism.unsupporting_people).stringify(0)
+                    activism
+                        .population
+                        .supporting_people()
+                        .stringify(0, self.notation),
+                    &(activism.population.supporting_people()
+                        + activism.population.unsupporting_people())
+                    .stringify(0, self.notation)
                 )
                 .into(),
             );
+
+            self.render_production(view);
         }
     }
 }
 mod prolog {
     use crate::{
         grid::{text::Text, Cell, MutGridView},
-        input::{Event, Input, Key},
         world::{quantity::Quantity, World},
     };
 
-    use super::{INITIAL_NUMBER_OF_FLYERS_AND_INVERSE_OF_PERSUASIVENESS, NUMBER_OF_PROLOG_STEPS};
+    use super::{
+        INITIAL_NUMBER_OF_FLYERS_AND_INVERSE_OF_PERSUASIVENESS, NUMBER_OF_PROLOG_STEPS,
+        PROLOG_MAXIMAL_EMISSION_DEFICIT,
+    };
 
     const FLYER_HANDOUT_TEXTS: [&str;
         INITIAL_NUMBER_OF_FLYERS_AND_INVERSE_OF_PERSUASIVENESS as usize] =
@@ -142,47 +185,54 @@ mod prolog {
     ];
 
     impl World {
-        pub(super) fn render_activism_prolog(
-            &mut self,
-            input: &Input,
-            view: MutGridView<'_, Cell>,
-        ) {
+        pub(super) fn render_activism_prolog(&self, view: MutGridView<'_, Cell>) {
             if self.cards.activism.flyer != Quantity::default() {
-                self.render_flyer_handout(input, view);
+                self.render_flyer_handout(view);
             } else {
-                self.render_flyer_print(input, view);
+                self.render_flyer_print(view);
             }
         }
 
-        fn render_flyer_handout(&mut self, input: &Input, mut view: MutGridView<'_, Cell>) {
+        fn render_flyer_handout(&self, mut view: MutGridView<'_, Cell>) {
             let text_id = (INITIAL_NUMBER_OF_FLYERS_AND_INVERSE_OF_PERSUASIVENESS
                 - self.cards.activism.flyer.whole_amount()) as usize;
             let text = FLYER_HANDOUT_TEXTS[text_id];
 
             view.print_overflowing(0, Text::new().raw(text));
+        }
 
-            if let Some(Event::Key(Key::H)) = input.event {
-                let success = self.handout_flyer();
-                assert!(success);
-            }
+        fn render_flyer_print(&self, mut view: MutGridView<'_, Cell>) {
+            let text = FLYER_PRINT_TEXTS[self.prolog_step()];
+
+            view.print_overflowing(0, Text::new().raw(text));
         }
 
-        fn render_flyer_print(&mut self, input: &Input, mut view: MutGridView<'_, Cell>) {
-            let step = match self.cards.activism.stage {
+        fn prolog_step(&self) -> usize {
+            match self.cards.activism.stage {
                 super::Stage::Prolog { step } => step,
                 super::Stage::Main => unreachable!(),
-            };
-            let text = FLYER_PRINT_TEXTS[step];
+            }
+        }
 
-            view.print_overflowing(0, Text::new().raw(text));
+        pub(super) fn handout_flyer_prolog(&mut self) {
+            let success = self.handout_flyer();
+            assert!(success);
+        }
 
-            if let Some(Event::Key(Key::F)) = input.event {
-                let success = self.manually_create_flyer();
-                assert_eq!(success, step == NUMBER_OF_PROLOG_STEPS - 1);
-                self.cards.activism.stage.step_forward();
-                if step == NUMBER_OF_PROLOG_STEPS - 2 {
-                    self.set_maximal_emission_deficit(Quantity::new(1000));
+        pub(super) fn print_flyer_prolog(&mut self) {
+            let step = self.prolog_step();
+            if self.manually_create_flyer() {
+                // Supporters may have saved enough for a flyer before the last step, which ends
+                // the prolog early.
+                if step <= NUMBER_OF_PROLOG_STEPS - 2 {
+                    self.set_maximal_emission_deficit(PROLOG_MAXIMAL_EMISSION_DEFICIT);
                 }
+                self.cards.activism.stage = super::Stage::Main;
+                return;
+            }
+            self.cards.activism.stage.step_forward();
+            if step == NUMBER_OF_PROLOG_STEPS - 2 {
+                self.set_maximal_emission_deficit(PROLOG_MAXIMAL_EMISSION_DEFICIT);
             }
         }
     }
@@ -190,31 +240,51 @@ mod prolog {
 
 const INITIAL_NUMBER_OF_FLYERS_AND_INVERSE_OF_PERSUASIVENESS: u128 = 10;
 const INITIAL_FLYER_PRINT_COST: Quantity<Emission> = Quantity::new(6);
+const INITIAL_MAXIMAL_FLYER: Quantity<Flyer> = Quantity::new(100);
 pub const INITIAL_FLYER_PERSUASIVENESS: Quantity<Person> =
     Quantity::fraction(1, INITIAL_NUMBER_OF_FLYERS_AND_INVERSE_OF_PERSUASIVENESS);
 const NUMBER_OF_PROLOG_STEPS: usize = 6;
+/// Granted near the end of the prolog, so the last flyer can be printed.
+const PROLOG_MAXIMAL_EMISSION_DEFICIT: Quantity<Emission> = Quantity::new(1000);
 
 // #[derive(Debug)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Activism {
-    stage: Stage,
+    pub(super) stage: Stage,
     pub emission_balance: Balance<Emission>,
     pub flyer: Quantity<Flyer>,
     pub total_number_of_flyers: Quantity<Flyer>,
-    pub supporting_people: Quantity<Person>,
-    pub unsupporting_people: Quantity<Person>,
+    #[serde(default)]
+    pub population: Population,
+    /// Supporters of saves from before regions, spread over the regions when loaded.
+    #[serde(default, rename = "supporting_people", skip_serializing)]
+    legacy_supporting_people: Quantity<Person>,
     pub save_rate_from_flyers: Rate<Emission>,
 
     pub next_unlock_people: Quantity<Person>,
     pub next_next_unlock_people: Quantity<Person>,
 
     pub flyer_persuasiveness: Quantity<Person>,
-    pub flyer_effectiveness: Rate<Emission>,
+    /// Added to the persuasiveness from research, e.g. by milestones.
+    #[serde(default)]
+    pub flyer_persuasiveness_bonus: Quantity<Person>,
+    pub flyer_effectiveness: RatePerUnit<Emission, Person>,
     pub flyer_print_cost: Quantity<Emission>,
 
     pub maximal_emission_deficit: Quantity<Emission>,
-    // pub maximal_flyer: Quantity<Flyer>,
+    /// Flyers that fit into storage from research, bought storage rooms add to it.
+    #[serde(default = "initial_maximal_flyer")]
+    pub maximal_flyer: Quantity<Flyer>,
     pub has_recycling: bool,
+    #[serde(default)]
+    pub production: Production,
+    #[serde(default)]
+    pub opinion: Opinion,
+}
+fn initial_maximal_flyer() -> Quantity<Flyer> {
+    INITIAL_MAXIMAL_FLYER
 }
+
 impl Activism {
     pub fn new() -> Activism {
         Self {
@@ -223,80 +293,176 @@ impl Activism {
             emission_balance: Balance::new(),
             flyer: Quantity::new(INITIAL_NUMBER_OF_FLYERS_AND_INVERSE_OF_PERSUASIVENESS),
             total_number_of_flyers: Quantity::default(),
-            supporting_people: Quantity::new(0),
-            unsupporting_people: Quantity::new(9_000_000_000),
+            population: Population::default(),
+            legacy_supporting_people: Quantity::default(),
             save_rate_from_flyers: Rate::default(),
 
             next_unlock_people: Quantity::new(4),
             next_next_unlock_people: Quantity::new(30),
 
             flyer_persuasiveness: INITIAL_FLYER_PERSUASIVENESS,
+            flyer_persuasiveness_bonus: Quantity::default(),
             flyer_effectiveness: FLYER_EFFECTIVENESS_0,
             flyer_print_cost: INITIAL_FLYER_PRINT_COST,
 
             maximal_emission_deficit: Quantity::default(),
-            // maximal_flyer: Quantity::new(100),
+            maximal_flyer: INITIAL_MAXIMAL_FLYER,
             has_recycling: false,
+            production: Production::default(),
+            opinion: Opinion::default(),
         }
     }
+
+    /// Only current supporters save emissions, at today's effectiveness.
+    /// Nobody saves more than they emit.
+    pub(super) fn update_save_rate(&mut self) {
+        let mut save_rate = Rate::default();
+        for (region, definition) in self.population.regions.iter().zip(&REGIONS) {
+            let emission = definition.per_capita_emission();
+            let saving = if self.flyer_effectiveness <= emission {
+                self.flyer_effectiveness
+            } else {
+                emission
+            };
+            save_rate += saving * region.supporting_people;
+        }
+        self.save_rate_from_flyers = save_rate;
+    }
 }
 
 impl World {
-    fn set_maximal_emission_deficit(&mut self, new_maximal_deficit: Quantity<Emission>) {
+    pub(super) fn set_maximal_emission_deficit(&mut self, new_maximal_deficit: Quantity<Emission>) {
         assert!(self.cards.activism.maximal_emission_deficit < new_maximal_deficit);
         self.cards.activism.maximal_emission_deficit = new_maximal_deficit;
 
         self.messages.queue(Message::new(
             format!(
                 "Increased maximal emission deficit to {}",
-                new_maximal_deficit.stringify(2)
+                new_maximal_deficit.stringify(2, self.notation)
             ),
             STANDARD_MESSAGE_DURATION,
         ))
     }
 
-    pub(super) fn render_card_activism(&mut self, input: &Input, view: MutGridView<'_, Cell>) {
+    pub(super) fn render_card_activism(&self, view: MutGridView<'_, Cell>) {
         match self.cards.activism.stage {
-            Stage::Prolog { .. } => self.render_activism_prolog(input, view),
-            Stage::Main => self.render_activism_main(input, view),
+            Stage::Prolog { .. } => self.render_activism_prolog(view),
+            Stage::Main => self.render_activism_main(view),
+        }
+    }
+
+    pub(super) fn activism_action_for_command(&self, command: Command) -> Option<Action> {
+        match command {
+            Command::PrintFlyer => Some(Action::PrintFlyer),
+            Command::HandoutFlyer => Some(Action::HandoutFlyer),
+            Command::Choose(1) => Some(Action::Build(Machine::Printer)),
+            Command::Choose(2) => Some(Action::Build(Machine::RecyclingStation)),
+            Command::Choose(3) => Some(Action::ExpandFlyerStorage),
+            _ => None,
+        }
+    }
+
+    /// In the prolog, flyers are only printed after all of them are handed out.
+    pub(crate) fn can_print_flyer_manually(&self) -> bool {
+        match self.cards.activism.stage {
+            Stage::Prolog { .. } => self.cards.activism.flyer == Quantity::default(),
+            Stage::Main => true,
+        }
+    }
+
+    /// Starts in the state the prolog ends in, for players who know the story already.
+    pub(super) fn skip_prolog(&mut self) {
+        assert!(self.is_in_prolog());
+        self.cards.activism.stage = Stage::Main;
+        self.set_maximal_emission_deficit(PROLOG_MAXIMAL_EMISSION_DEFICIT);
+    }
+
+    #[cfg(test)]
+    pub(super) fn new_after_prolog() -> Self {
+        let mut world = Self::new();
+        world.skip_prolog();
+        world
+    }
+
+    pub(super) fn is_in_prolog(&self) -> bool {
+        matches!(self.cards.activism.stage, Stage::Prolog { .. })
+    }
+
+    pub(crate) fn can_handout_flyer_manually(&self) -> bool {
+        match self.cards.activism.stage {
+            Stage::Prolog { .. } => self.cards.activism.flyer != Quantity::default(),
+            Stage::Main => true,
+        }
+    }
+
+    pub(crate) fn print_flyer_manually(&mut self) {
+        assert!(self.can_print_flyer_manually());
+        match self.cards.activism.stage {
+            Stage::Prolog { .. } => self.print_flyer_prolog(),
+            Stage::Main => self.print_flyer_main(),
+        }
+    }
+
+    pub(crate) fn handout_flyer_manually(&mut self) {
+        assert!(self.can_handout_flyer_manually());
+        match self.cards.activism.stage {
+            Stage::Prolog { .. } => self.handout_flyer_prolog(),
+            Stage::Main => {
+                self.handout_flyer();
+            }
         }
     }
 
     pub(super) fn simulate_card_activism(&mut self, delta: Duration) {
         let co2_card = &mut self.cards.activism;
+        if co2_card.legacy_supporting_people != Quantity::default() {
+            co2_card
+                .population
+                .spread(co2_card.legacy_supporting_people);
+            co2_card.legacy_supporting_people = Quantity::default();
+        }
+        co2_card.update_save_rate();
 
-        *co2_card.emission_balance.pos_mut() += co2_card.save_rate_from_flyers * delta;
+        co2_card
+            .emission_balance
+            .credit(co2_card.save_rate_from_flyers.saturating_mul(delta));
 
-        while co2_card.next_unlock_people <= co2_card.supporting_people {
+        while co2_card.next_unlock_people <= co2_card.population.supporting_people() {
             let sum = co2_card.next_unlock_people + co2_card.next_next_unlock_people;
             co2_card.next_unlock_people = co2_card.next_next_unlock_people;
             co2_card.next_next_unlock_people = sum;
 
             self.cards.staff.researcher += 1;
         }
+
+        self.simulate_production(delta);
+        self.simulate_opinion(delta);
     }
 
     fn handout_flyer(&mut self) -> bool {
-        let activism = &mut self.cards.activism;
+        self.handout_flyers(1) == 1
+    }
 
-        if activism.unsupporting_people > Quantity::default()
-            && activism.flyer.try_pay(Quantity::new(1))
-        {
-            let previous_supporting_people = activism.supporting_people.whole_amount();
-            let reduction = activism
-                .unsupporting_people
-                .saturating_sub(activism.flyer_persuasiveness);
-            activism.supporting_people += reduction;
-            let new_supporters =
-                activism.supporting_people.whole_amount() - previous_supporting_people;
-            assert!(new_supporters == 0 || new_supporters == 1);
-
-            activism.save_rate_from_flyers += new_supporters * activism.flyer_effectiveness;
-
-            true
-        } else {
-            false
+    /// Hands out up to `count` flyers at once in the target region
+    /// and returns how many were handed out.
+    pub(super) fn handout_flyers(&mut self, count: u128) -> u128 {
+        let activism = &mut self.cards.activism;
+        let target = activism.population.flyer_target;
+        if activism.population.regions[target].unsupporting_people == Quantity::default() {
+            return 0;
         }
+
+        let count = count.min(activism.flyer.whole_amount());
+        activism.flyer -= Quantity::new(count);
+
+        let (numerator, denominator) = REGIONS[target].persuasion;
+        let persuasiveness = activism.flyer_persuasiveness + activism.flyer_persuasiveness_bonus;
+        let (convinced, _) =
+            (count * numerator * persuasiveness).divide_with_remainder(denominator);
+        activism.population.regions[target].convince(convinced);
+        activism.update_save_rate();
+
+        count
     }
 
     fn manually_create_flyer(&mut self) -> bool {
@@ -308,24 +474,36 @@ impl World {
     }
 
     fn has_room_for_one_more_flyer(&self) -> bool {
-        true
-        // self.cards.activism.flyer + Quantity::new(1) <= self.cards.activism.maximal_flyer
+        self.room_for_flyers() != 0
     }
 
-    fn print_flyer(&mut self) -> bool {
-        if !self.has_room_for_one_more_flyer() {
-            return false;
-        }
+    /// Savings of the supporters minus what the printers emit.
+    pub(super) fn net_emission_rate(&self) -> SignedRate<Emission> {
+        self.cards.activism.save_rate_from_flyers - self.printing_emission_rate()
+    }
 
-        let mut theoretical_balance = self.cards.activism.emission_balance;
-        *theoretical_balance.neg_mut() += self.cards.activism.flyer_print_cost;
-        *theoretical_balance.pos_mut() += self.cards.activism.maximal_emission_deficit;
+    /// Whether `cost` can be spent without exceeding the maximal emission deficit.
+    pub(super) fn can_pay_emission(&self, cost: Quantity<Emission>) -> bool {
+        let activism = &self.cards.activism;
+        activism.emission_balance.balance() - cost
+            >= -SignedQuantity::from(activism.maximal_emission_deficit)
+    }
+
+    pub(super) fn pay_emission(&mut self, cost: Quantity<Emission>) {
+        assert!(self.can_pay_emission(cost));
+        self.cards.activism.emission_balance.debit(cost);
+    }
 
-        if theoretical_balance.balance() < Quantity::default() {
+    fn print_flyer(&mut self) -> bool {
+        if !self.has_room_for_one_more_flyer()
+            || !self.can_pay_emission(self.cards.activism.flyer_print_cost)
+            || !self.can_pay_money(FLYER_PRINT_PRICE)
+        {
             return false;
         }
 
-        *self.cards.activism.emission_balance.neg_mut() += self.cards.activism.flyer_print_cost;
+        self.pay_emission(self.cards.activism.flyer_print_cost);
+        self.pay_money(FLYER_PRINT_PRICE);
         self.cards.activism.flyer += 1;
         self.cards.activism.total_number_of_flyers += 1;
         true
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    duration::Duration,
+    world::{
+        quantity::{types::Person, Quantity},
+        World,
+    },
+};
+
+/// Time a supporter needs to convince one more person, while nearly everyone is unconvinced.
+const WORD_OF_MOUTH_TIME: Duration = Duration::from_seconds(20 * 24 * 60 * 60);
+pub const INITIAL_SUPPORTER_LIFETIME: Duration = Duration::from_seconds(10 * 24 * 60 * 60);
+/// Opinions change in whole steps, so the tiny changes of short frames are not rounded away.
+const OPINION_STEP: Duration = Duration::MINUTE;
+/// Resolution of opinion changes, in people.
+const PEOPLE_RESOLUTION: u128 = 1_000_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Opinion {
+    pub supporter_lifetime: Duration,
+    /// Time not accounted for yet, shorter than [`OPINION_STEP`].
+    pending: Duration,
+}
+
+impl Default for Opinion {
+    fn default() -> Self {
+        Self {
+            supporter_lifetime: INITIAL_SUPPORTER_LIFETIME,
+            pending: Duration::INSTANT,
+        }
+    }
+}
+
+/// Supporters after `elapsed`, the exact solution of the logistic equation
+/// `dS/dt = S (N - S) / (N * WORD_OF_MOUTH_TIME) - S / lifetime` for supporters `S` among a
+/// population `N`. Written so that long absences cannot overflow.
+fn supporters_after(
+    supporters: f64,
+    population: f64,
+    lifetime: Duration,
+    elapsed: Duration,
+) -> f64 {
+    let time = elapsed.ticks() as f64;
+    let contact = 1.0 / WORD_OF_MOUTH_TIME.ticks() as f64 / population;
+    let growth = 1.0 / WORD_OF_MOUTH_TIME.ticks() as f64 - 1.0 / lifetime.ticks() as f64;
+
+    if growth > 0.0 {
+        let decay = (-growth * time).exp();
+        supporters / (decay + contact * supporters * -(-growth * time).exp_m1() / growth)
+    } else if growth < 0.0 {
+        supporters * (growth * time).exp()
+            / (1.0 + contact * supporters * (growth * time).exp_m1() / growth)
+    } else {
+        supporters / (1.0 + contact * supporters * time)
+    }
+}
+
+fn people(amount: f64) -> Quantity<Person> {
+    let parts = (amount * PEOPLE_RESOLUTION as f64).round() as u128;
+    Quantity::new(parts / PEOPLE_RESOLUTION)
+        + Quantity::fraction(parts % PEOPLE_RESOLUTION, PEOPLE_RESOLUTION)
+}
+
+impl World {
+    pub(super) fn simulate_opinion(&mut self, delta: Duration) {
+        if self.is_in_prolog() {
+            return;
+        }
+
+        let activism = &mut self.cards.activism;
+        let opinion = &mut activism.opinion;
+        opinion.pending += delta;
+        let steps = opinion.pending.ticks() / OPINION_STEP.ticks();
+        if steps == 0 {
+            return;
+        }
+        let elapsed = steps * OPINION_STEP;
+        opinion.pending -= elapsed;
+
+        for region in &mut activism.population.regions {
+            let supporters = region.supporting_people.as_f64();
+            if supporters == 0.0 {
+                continue;
+            }
+            let population = supporters + region.unsupporting_people.as_f64();
+            let new_supporters =
+                supporters_after(supporters, population, opinion.supporter_lifetime, elapsed);
+            if supporters <= new_supporters {
+                region.convince(people(new_supporters - supporters));
+            } else {
+                let lapsed = region
+                    .supporting_people
+                    .take_up_to(people(supporters - new_supporters));
+                region.unsupporting_people += lapsed;
+            }
+        }
+
+        activism.update_save_rate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        world::{quantity::Quantity, World},
+    };
+
+    use super::supporters_after;
+
+    const DAY: u128 = 24 * 60 * 60;
+
+    fn world_with_supporters(supporters: u128) -> World {
+        let mut world = World::new_after_prolog();
+        world.cards.activism.population.regions[0].convince(Quantity::new(supporters));
+        world
+    }
+
+    #[test]
+    fn supporters_lapse_without_retention() {
+        let mut world = world_with_supporters(1_000);
+        world.simulate(Duration::from_seconds(10 * DAY));
+
+        // Word of mouth adds 1/20 per day, lapsing takes 1/10, so e^-0.5 of them remain.
+        let activism = &world.cards.activism;
+        let supporters = activism.population.supporting_people().whole_amount();
+        assert!((600..610).contains(&supporters), "{supporters}");
+        assert_eq!(
+            (activism.population.supporting_people() + activism.population.unsupporting_people())
+                .whole_amount(),
+            9_000_000_000
+        );
+        assert_eq!(
+            activism.save_rate_from_flyers,
+            activism.flyer_effectiveness * activism.population.supporting_people()
+        );
+    }
+
+    #[test]
+    fn word_of_mouth_spreads_with_retention() {
+        let mut world = world_with_supporters(1_000);
+        world.cards.activism.opinion.supporter_lifetime = Duration::from_seconds(90 * DAY);
+        world.simulate(Duration::from_seconds(10 * DAY));
+        assert!(world.cards.activism.population.supporting_people() > Quantity::new(1_400));
+    }
+
+    #[test]
+    fn long_absences_approach_the_equilibrium() {
+        // Word of mouth and lapsing balance out at 1 - 20 / 90 of the population.
+        let supporters = supporters_after(
+            1_000.0,
+            9e9,
+            Duration::from_seconds(90 * DAY),
+            Duration::from_seconds(100 * 365 * DAY),
+        );
+        assert!((6.99e9..7.01e9).contains(&supporters), "{supporters}");
+    }
+}
@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    duration::Duration,
+    grid::{text::Text, Cell, Color, MutGridView},
+    world::{
+        cards::funding::FLYER_PRINT_PRICE,
+        message::{Message, STANDARD_MESSAGE_DURATION},
+        quantity::{
+            types::{Emission, Flyer},
+            Quantity,
+        },
+        rate::Rate,
+        World,
+    },
+};
+
+pub const PRINTER_RATE: Rate<Flyer> = Rate::new(Quantity::new(1), Duration::from_seconds(20));
+pub const RECYCLING_STATION_RATE: Rate<Flyer> =
+    Rate::new(Quantity::new(1), Duration::from_seconds(60));
+const STORAGE_ROOM_CAPACITY: Quantity<Flyer> = Quantity::new(100);
+const STORAGE_ROOM_COST: Quantity<Emission> = Quantity::new(1_000);
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Machine {
+    /// Prints flyers, each paid with emissions and money like a manually printed one.
+    Printer,
+    /// Makes flyers from waste paper for free, once recycling is researched.
+    RecyclingStation,
+}
+
+impl Machine {
+    fn build_cost(self) -> Quantity<Emission> {
+        match self {
+            Machine::Printer => Quantity::new(500),
+            Machine::RecyclingStation => Quantity::new(2_000),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Production {
+    printers: u128,
+    recycling_stations: u128,
+    /// Flyers about to be printed, only the fractional part is kept while paused.
+    printing_progress: Quantity<Flyer>,
+    recycling_progress: Quantity<Flyer>,
+    /// Whether the printers stopped at the emission deficit limit or for lack of money.
+    is_paused: bool,
+    #[serde(default)]
+    storage_rooms: u128,
+}
+
+impl Production {
+    fn machines_mut(&mut self, machine: Machine) -> &mut u128 {
+        match machine {
+            Machine::Printer => &mut self.printers,
+            Machine::RecyclingStation => &mut self.recycling_stations,
+        }
+    }
+}
+
+impl World {
+    pub(super) fn render_production(&self, mut view: MutGridView<'_, Cell>) {
+        let activism = &self.cards.activism;
+        let production = &activism.production;
+
+        view.print(
+            4,
+            0,
+            format!(
+                "Printers: {} +{}",
+                production.printers,
+                (production.printers * PRINTER_RATE).stringify(2, self.notation)
+            )
+            .into(),
+        );
+        view.print(
+            6,
+            0,
+            format!(
+                "1: printer ({})",
+                Machine::Printer.build_cost().stringify(0, self.notation)
+            )
+            .into(),
+        );
+        if activism.has_recycling {
+            view.print(
+                5,
+                0,
+                format!(
+                    "Recyclers: {} +{}",
+                    production.recycling_stations,
+                    (production.recycling_stations * RECYCLING_STATION_RATE)
+                        .stringify(2, self.notation)
+                )
+                .into(),
+            );
+            view.print(
+                7,
+                0,
+                format!(
+                    "2: recycler ({})",
+                    Machine::RecyclingStation
+                        .build_cost()
+                        .stringify(0, self.notation)
+                )
+                .into(),
+            );
+        }
+        view.print(
+            8,
+            0,
+            format!(
+                "3: storage ({})",
+                STORAGE_ROOM_COST.stringify(0, self.notation)
+            )
+            .into(),
+        );
+
+        let has_machines = production.printers != 0
+            || (activism.has_recycling && production.recycling_stations != 0);
+        let status = if has_machines && self.room_for_flyers() == 0 {
+            Some("Storage full")
+        } else if production.is_paused {
+            Some("Printing paused")
+        } else {
+            None
+        };
+        if let Some(status) = status {
+            view.print(9, 0, Text::new().styled(status, Some(Color::RED), None));
+        }
+    }
+
+    /// Emissions of the printers, as long as they are running.
+    pub(super) fn printing_emission_rate(&self) -> Rate<Emission> {
+        let activism = &self.cards.activism;
+        let production = &activism.production;
+        if production.is_paused || self.room_for_flyers() == 0 {
+            return Rate::default();
+        }
+        (production.printers * PRINTER_RATE).cost_at(activism.flyer_print_cost)
+    }
+
+    pub(super) fn flyer_capacity(&self) -> Quantity<Flyer> {
+        let activism = &self.cards.activism;
+        activism.maximal_flyer + activism.production.storage_rooms * STORAGE_ROOM_CAPACITY
+    }
+
+    pub(super) fn room_for_flyers(&self) -> u128 {
+        self.flyer_capacity()
+            .whole_amount()
+            .saturating_sub(self.cards.activism.flyer.whole_amount())
+    }
+
+    pub(crate) fn can_expand_flyer_storage(&self) -> bool {
+        !self.is_in_prolog() && self.can_pay_emission(STORAGE_ROOM_COST)
+    }
+
+    pub(crate) fn expand_flyer_storage(&mut self) {
+        assert!(self.can_expand_flyer_storage());
+        self.pay_emission(STORAGE_ROOM_COST);
+        self.cards.activism.production.storage_rooms += 1;
+    }
+
+    pub(crate) fn can_build(&self, machine: Machine) -> bool {
+        let activism = &self.cards.activism;
+        !self.is_in_prolog()
+            && (machine != Machine::RecyclingStation || activism.has_recycling)
+            && self.can_pay_emission(machine.build_cost())
+    }
+
+    pub(crate) fn build(&mut self, machine: Machine) {
+        assert!(self.can_build(machine));
+        self.pay_emission(machine.build_cost());
+        *self.cards.activism.production.machines_mut(machine) += 1;
+    }
+
+    /// How many of `wanted` printed flyers fit into the maximal emission deficit and the money.
+    fn affordable_flyers(&self, wanted: u128) -> u128 {
+        let cost = self.cards.activism.flyer_print_cost;
+        let (mut low, mut high) = (0, wanted);
+        while low < high {
+            let middle = low + (high - low).div_ceil(2);
+            if self.can_pay_emission(middle * cost)
+                && self.can_pay_money(middle * FLYER_PRINT_PRICE)
+            {
+                low = middle;
+            } else {
+                high = middle - 1;
+            }
+        }
+        low
+    }
+
+    /// All flyers of the time step are produced as one batch, however long it is.
+    /// Flyers that do not fit into storage are never made.
+    pub(super) fn simulate_production(&mut self, delta: Duration) {
+        if self.is_in_prolog() {
+            return;
+        }
+
+        let room = self.room_for_flyers();
+        let activism = &mut self.cards.activism;
+        let production = &mut activism.production;
+        if activism.has_recycling {
+            production.recycling_progress +=
+                (production.recycling_stations * RECYCLING_STATION_RATE) * delta;
+            let finished = production.recycling_progress.whole_amount();
+            production.recycling_progress -= Quantity::new(finished);
+            let recycled = finished.min(room);
+            activism.flyer += recycled;
+            activism.total_number_of_flyers += recycled;
+        }
+
+        let room = self.room_for_flyers();
+        let production = &mut self.cards.activism.production;
+        production.printing_progress += (production.printers * PRINTER_RATE) * delta;
+        let planned = production.printing_progress.whole_amount();
+        let printable = planned.min(room);
+        let printed = self.affordable_flyers(printable);
+        self.pay_emission(printed * self.cards.activism.flyer_print_cost);
+        self.pay_money(printed * FLYER_PRINT_PRICE);
+
+        let activism = &mut self.cards.activism;
+        activism.flyer += printed;
+        activism.total_number_of_flyers += printed;
+
+        // Printers at the deficit limit wait instead of building up a backlog.
+        let production = &mut activism.production;
+        production.printing_progress -= Quantity::new(planned);
+        let was_paused = production.is_paused;
+        production.is_paused = printed < printable;
+        if production.is_paused && !was_paused {
+            self.messages.queue(Message::new(
+                "Printers paused, no emissions or money left to spend.".to_owned(),
+                STANDARD_MESSAGE_DURATION,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        world::{action::Action, quantity::Quantity, rate::Rate, World},
+    };
+
+    use super::Machine;
+
+    #[test]
+    fn printers_pause_at_deficit_limit() {
+        let mut world = World::new_after_prolog();
+        world.cards.activism.flyer = Quantity::default();
+        world.cards.activism.maximal_emission_deficit = Quantity::new(560);
+
+        assert!(!world.apply_action(Action::Build(Machine::RecyclingStation)));
+        assert!(world.apply_action(Action::Build(Machine::Printer)));
+        assert!(!world.apply_action(Action::Build(Machine::Printer)));
+
+        // 60g are left for 10 flyers at 6g each, the rest of the day is skipped.
+        world.simulate(Duration::from_seconds(24 * 60 * 60));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(10));
+        assert!(world.cards.activism.production.is_paused);
+        assert!(world.cards.activism.production.printing_progress < Quantity::new(1));
+        assert_eq!(world.printing_emission_rate(), Rate::default());
+
+        // Production resumes once there is emission to spend again.
+        world
+            .cards
+            .activism
+            .emission_balance
+            .credit(Quantity::new(12));
+        world.simulate(Duration::from_seconds(24 * 60 * 60 + 40));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(12));
+        assert!(!world.cards.activism.production.is_paused);
+        // 6g every 20s, with nothing saved.
+        assert_eq!(
+            -world.net_emission_rate().per(Duration::MINUTE),
+            Quantity::new(18)
+        );
+    }
+
+    #[test]
+    fn production_stops_at_storage_capacity() {
+        let mut world = World::new_after_prolog();
+        world.cards.activism.has_recycling = true;
+        world.cards.activism.maximal_emission_deficit = Quantity::new(10_000);
+        assert!(world.apply_action(Action::Build(Machine::RecyclingStation)));
+        assert!(world.apply_action(Action::Build(Machine::Printer)));
+
+        world.simulate(Duration::from_seconds(24 * 60 * 60));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(100));
+        assert!(!world.cards.activism.production.is_paused);
+        world.apply_action(Action::PrintFlyer);
+        assert_eq!(world.cards.activism.flyer, Quantity::new(100));
+
+        assert!(world.apply_action(Action::ExpandFlyerStorage));
+        world.simulate(Duration::from_seconds(2 * 24 * 60 * 60));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(200));
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    duration::Duration,
+    world::{
+        quantity::{
+            types::{Emission, Person},
+            Quantity,
+        },
+        rate::{Rate, RatePerUnit},
+    },
+};
+
+pub struct RegionDefinition {
+    pub name: &'static str,
+    pub symbol: char,
+    population: u128,
+    pub per_capita_tonnes: u128,
+    /// Flyers convince this many times the usual number of people, as (numerator, denominator).
+    pub persuasion: (u128, u128),
+}
+
+impl RegionDefinition {
+    /// Emissions of one person per year, at most this much can be saved by a supporter.
+    pub fn per_capita_emission(&self) -> RatePerUnit<Emission, Person> {
+        RatePerUnit::new(Rate::new(
+            Quantity::new(self.per_capita_tonnes * 1_000_000),
+            Duration::YEAR,
+        ))
+    }
+}
+
+pub const REGIONS: [RegionDefinition; 6] = [
+    RegionDefinition {
+        name: "North America",
+        symbol: 'N',
+        population: 600_000_000,
+        per_capita_tonnes: 14,
+        persuasion: (1, 2),
+    },
+    RegionDefinition {
+        name: "Latin America",
+        symbol: 'L',
+        population: 700_000_000,
+        per_capita_tonnes: 3,
+        persuasion: (1, 1),
+    },
+    RegionDefinition {
+        name: "Europe",
+        symbol: 'E',
+        population: 750_000_000,
+        per_capita_tonnes: 6,
+        persuasion: (1, 1),
+    },
+    RegionDefinition {
+        name: "Africa",
+        symbol: 'F',
+        population: 1_650_000_000,
+        per_capita_tonnes: 1,
+        persuasion: (3, 2),
+    },
+    RegionDefinition {
+        name: "Asia",
+        symbol: 'A',
+        population: 5_200_000_000,
+        per_capita_tonnes: 4,
+        persuasion: (1, 1),
+    },
+    RegionDefinition {
+        name: "Oceania",
+        symbol: 'O',
+        population: 100_000_000,
+        per_capita_tonnes: 10,
+        persuasion: (1, 2),
+    },
+];
+
+/// Flyers convince as many people there as the prolog promises.
+const INITIAL_FLYER_TARGET: usize = 2;
+
+/// Emissions of everyone, supporters included.
+pub fn world_emission() -> Rate<Emission> {
+    let mut emission = Rate::default();
+    for definition in &REGIONS {
+        emission += definition.per_capita_emission() * Quantity::new(definition.population);
+    }
+    emission
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Region {
+    pub supporting_people: Quantity<Person>,
+    pub unsupporting_people: Quantity<Person>,
+}
+
+impl Region {
+    /// Convinces up to `people` and returns how many were convinced.
+    pub fn convince(&mut self, people: Quantity<Person>) -> Quantity<Person> {
+        let convinced = self.unsupporting_people.take_up_to(people);
+        self.supporting_people += convinced;
+        convinced
+    }
+
+    pub fn support_share(&self) -> f64 {
+        let population = self.supporting_people + self.unsupporting_people;
+        self.supporting_people.as_f64() / population.as_f64()
+    }
+}
+
+/// Regions are stored in the order of [`REGIONS`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Population {
+    pub regions: Vec<Region>,
+    pub flyer_target: usize,
+}
+
+impl Default for Population {
+    fn default() -> Self {
+        Self {
+            regions: REGIONS
+                .iter()
+                .map(|definition| Region {
+                    supporting_people: Quantity::default(),
+                    unsupporting_people: Quantity::new(definition.population),
+                })
+                .collect(),
+            flyer_target: INITIAL_FLYER_TARGET,
+        }
+    }
+}
+
+impl Population {
+    pub fn supporting_people(&self) -> Quantity<Person> {
+        self.regions
+            .iter()
+            .fold(Quantity::default(), |sum, region| {
+                sum + region.supporting_people
+            })
+    }
+
+    pub fn unsupporting_people(&self) -> Quantity<Person> {
+        self.regions
+            .iter()
+            .fold(Quantity::default(), |sum, region| {
+                sum + region.unsupporting_people
+            })
+    }
+
+    /// Convinces `people` spread over the regions by their size.
+    pub fn spread(&mut self, people: Quantity<Person>) {
+        let total: u128 = REGIONS.iter().map(|definition| definition.population).sum();
+        for (region, definition) in self.regions.iter_mut().zip(&REGIONS) {
+            let (share, _) = (definition.population * people).divide_with_remainder(total);
+            region.convince(share);
+        }
+    }
+}
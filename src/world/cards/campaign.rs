@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    duration::Duration,
+    grid::{text::Text, Cell, Color, MutGridView},
+    input::Command,
+    world::{
+        action::Action,
+        message::{Message, STANDARD_MESSAGE_DURATION},
+        quantity::{
+            types::{Person, ResearchPoints},
+            Quantity,
+        },
+        rate::{Rate, RatePerUnit},
+        World,
+    },
+};
+
+use super::{abstract_card::AbstractCard, Card, Cards};
+
+/// Saved emissions worth one inspiration, one tonne.
+const GRAMS_PER_INSPIRATION: u128 = 1_000_000;
+const FLYERS_PER_INSPIRATION: u128 = 1_000;
+const PERSUASIVENESS_PER_LEVEL: Quantity<Person> = Quantity::fraction(1, 100);
+const RESEARCH_RATE_PER_LEVEL: RatePerUnit<ResearchPoints, Person> =
+    RatePerUnit::new(Rate::new(Quantity::new(1), Duration::from_seconds(10 * 60)));
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Bonus {
+    Persuasiveness,
+    ResearchRate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Campaign {
+    discovered: bool,
+    ended: u128,
+    /// Earned by ending campaigns, spent on bonuses.
+    inspiration: u128,
+    persuasiveness_level: u128,
+    research_rate_level: u128,
+}
+
+impl Campaign {
+    pub fn new() -> Campaign {
+        Self {
+            discovered: false,
+            ended: 0,
+            inspiration: 0,
+            persuasiveness_level: 0,
+            research_rate_level: 0,
+        }
+    }
+
+    fn level_mut(&mut self, bonus: Bonus) -> &mut u128 {
+        match bonus {
+            Bonus::Persuasiveness => &mut self.persuasiveness_level,
+            Bonus::ResearchRate => &mut self.research_rate_level,
+        }
+    }
+
+    fn level(&self, bonus: Bonus) -> u128 {
+        match bonus {
+            Bonus::Persuasiveness => self.persuasiveness_level,
+            Bonus::ResearchRate => self.research_rate_level,
+        }
+    }
+
+    /// Each level costs one inspiration more than the previous one.
+    fn cost(&self, bonus: Bonus) -> u128 {
+        self.level(bonus) + 1
+    }
+}
+
+impl AbstractCard for Campaign {
+    fn menu_string(&self) -> String {
+        "Campaign".into()
+    }
+
+    fn color(&self) -> Color {
+        Color::ORANGE
+    }
+
+    fn is_visible(&self) -> bool {
+        self.discovered
+    }
+}
+
+impl World {
+    /// Inspiration the current campaign is worth when it ends now.
+    fn earned_inspiration(&self) -> u128 {
+        self.cards.saved_emission().whole_amount() / GRAMS_PER_INSPIRATION
+            + self.cards.activism.total_number_of_flyers.whole_amount() / FLYERS_PER_INSPIRATION
+    }
+
+    pub(super) fn render_card_campaign(&self, mut view: MutGridView<'_, Cell>) {
+        let campaign = &self.cards.campaign;
+
+        view.print(0, 0, format!("Campaign {}", campaign.ended + 1).into());
+        view.print(
+            1,
+            0,
+            format!("Inspiration: {}", campaign.inspiration).into(),
+        );
+        view.print(
+            2,
+            0,
+            format!(" on end: +{}", self.earned_inspiration()).into(),
+        );
+        view.print(
+            4,
+            0,
+            format!(
+                "1: convince L{} ({})",
+                campaign.persuasiveness_level,
+                campaign.cost(Bonus::Persuasiveness)
+            )
+            .into(),
+        );
+        view.print(
+            5,
+            0,
+            format!(
+                "2: research L{} ({})",
+                campaign.research_rate_level,
+                campaign.cost(Bonus::ResearchRate)
+            )
+            .into(),
+        );
+        view.print(7, 0, "e: end campaign".to_owned().into());
+        if self.cards.activism.population.unsupporting_people() == Quantity::default() {
+            view.print(
+                9,
+                0,
+                Text::new().styled("Everyone is convinced", Some(Color::GREEN), None),
+            );
+        }
+    }
+
+    pub(super) fn campaign_action_for_command(&self, command: Command) -> Option<Action> {
+        match command {
+            Command::Choose(1) => Some(Action::BuyBonus(Bonus::Persuasiveness)),
+            Command::Choose(2) => Some(Action::BuyBonus(Bonus::ResearchRate)),
+            Command::EndCampaign => Some(Action::EndCampaign),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn can_buy_bonus(&self, bonus: Bonus) -> bool {
+        let campaign = &self.cards.campaign;
+        campaign.discovered && campaign.cost(bonus) <= campaign.inspiration
+    }
+
+    /// The bonus also applies to the running campaign.
+    pub(crate) fn buy_bonus(&mut self, bonus: Bonus) {
+        assert!(self.can_buy_bonus(bonus));
+        let campaign = &mut self.cards.campaign;
+        campaign.inspiration -= campaign.cost(bonus);
+        *campaign.level_mut(bonus) += 1;
+        self.apply_bonus(bonus, 1);
+    }
+
+    fn apply_bonus(&mut self, bonus: Bonus, levels: u128) {
+        match bonus {
+            Bonus::Persuasiveness => {
+                self.cards.activism.flyer_persuasiveness_bonus += levels * PERSUASIVENESS_PER_LEVEL
+            }
+            Bonus::ResearchRate => {
+                self.cards.research.rate_per_researcher += levels * RESEARCH_RATE_PER_LEVEL
+            }
+        }
+    }
+
+    pub(crate) fn can_end_campaign(&self) -> bool {
+        self.cards.campaign.discovered && self.earned_inspiration() != 0
+    }
+
+    /// Starts over with fresh cards, only the campaign card, the climate and the time are kept.
+    /// The prolog is not told again.
+    pub(crate) fn end_campaign(&mut self) {
+        assert!(self.can_end_campaign());
+        let mut campaign = self.cards.campaign.clone();
+        campaign.inspiration += self.earned_inspiration();
+        campaign.ended += 1;
+        let climate = self.cards.climate.clone();
+
+        self.cards = Cards::new(self.cards.research.manager.tree().clone());
+        self.cards.campaign = campaign;
+        self.cards.climate = climate;
+        self.cards.selected = Card::Campaign;
+        self.skip_prolog();
+        for bonus in [Bonus::Persuasiveness, Bonus::ResearchRate] {
+            self.apply_bonus(bonus, self.cards.campaign.level(bonus));
+        }
+
+        self.messages.queue(Message::new(
+            format!("Campaign {} started.", self.cards.campaign.ended + 1),
+            STANDARD_MESSAGE_DURATION,
+        ));
+    }
+
+    pub(super) fn simulate_card_campaign(&mut self, _delta: Duration) {
+        if !self.cards.campaign.discovered && self.earned_inspiration() != 0 {
+            self.cards.campaign.discovered = true;
+            self.messages.queue(Message::new(
+                "Your campaign can inspire the next one.".into(),
+                STANDARD_MESSAGE_DURATION,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        world::{action::Action, quantity::Quantity, World},
+    };
+
+    use super::Bonus;
+
+    #[test]
+    fn ending_a_campaign_keeps_bonuses() {
+        let mut world = World::new();
+        assert!(!world.apply_action(Action::EndCampaign));
+
+        world.skip_prolog();
+        world.cards.activism.total_number_of_flyers = Quantity::new(3_000);
+        world
+            .cards
+            .activism
+            .emission_balance
+            .credit(Quantity::new(2_000_000));
+        world.simulate(Duration::SECOND);
+        assert!(world.apply_action(Action::EndCampaign));
+
+        let campaign = &world.cards.campaign;
+        assert_eq!((campaign.ended, campaign.inspiration), (1, 5));
+        assert_eq!(
+            world.cards.activism.total_number_of_flyers,
+            Quantity::default()
+        );
+        assert!(!world.is_in_prolog());
+        assert_eq!(world.get_total_ticks(), Duration::SECOND);
+
+        let rate_per_researcher = world.cards.research.rate_per_researcher;
+        assert!(world.apply_action(Action::BuyBonus(Bonus::ResearchRate)));
+        assert!(world.apply_action(Action::BuyBonus(Bonus::ResearchRate)));
+        assert!(!world.apply_action(Action::BuyBonus(Bonus::ResearchRate)));
+        assert!(world.apply_action(Action::BuyBonus(Bonus::Persuasiveness)));
+        assert_eq!(world.cards.campaign.inspiration, 1);
+
+        world
+            .cards
+            .activism
+            .emission_balance
+            .credit(Quantity::new(1_000_000));
+        world.simulate(Duration::MINUTE);
+        assert!(world.apply_action(Action::EndCampaign));
+        assert_eq!(world.cards.campaign.inspiration, 2);
+        assert!(rate_per_researcher != world.cards.research.rate_per_researcher);
+        assert_eq!(
+            world.cards.activism.flyer_persuasiveness_bonus,
+            Quantity::fraction(1, 100)
+        );
+    }
+}
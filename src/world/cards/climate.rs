@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    duration::Duration,
+    grid::{text::Text, Cell, Color, MutGridView},
+    world::{
+        message::{Message, STANDARD_MESSAGE_DURATION},
+        quantity::{types::Emission, Quantity},
+        rate::Rate,
+        World,
+    },
+};
+
+use super::{abstract_card::AbstractCard, activism::regions::world_emission, Cards};
+
+/// What oceans and forests take up again, emissions below this do not warm the climate.
+const NATURAL_SINK: Rate<Emission> =
+    Rate::new(Quantity::new(8_000_000_000_000_000), Duration::YEAR);
+/// Emissions left until warming crosses [`WARMING_THRESHOLD`].
+const CARBON_BUDGET: Quantity<Emission> = Quantity::new(1_200_000_000_000_000_000);
+const WARMING_AT_START: f64 = 1.2;
+const WARMING_THRESHOLD: f64 = 2.0;
+const FIRST_YEAR: u128 = 2025;
+/// Climate years that pass in one year of the game, so the budget runs out within weeks.
+const TIME_COMPRESSION: u128 = 365;
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Outcome {
+    /// Emissions fell below what nature takes up, warming stopped.
+    Victory,
+    /// The carbon budget ran out.
+    Failure,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Climate {
+    discovered: bool,
+    /// Time that passed for the climate, faster than for the campaign.
+    elapsed: Duration,
+    used_budget: Quantity<Emission>,
+    outcome: Option<Outcome>,
+}
+
+impl Climate {
+    pub fn new() -> Climate {
+        Self {
+            discovered: false,
+            elapsed: Duration::INSTANT,
+            used_budget: Quantity::default(),
+            outcome: None,
+        }
+    }
+
+    fn year(&self) -> u128 {
+        FIRST_YEAR + self.elapsed.ticks() / Duration::YEAR.ticks()
+    }
+
+    /// Warming above pre-industrial levels, growing linearly with the used budget.
+    pub(super) fn projected_warming(&self) -> f64 {
+        WARMING_AT_START
+            + (WARMING_THRESHOLD - WARMING_AT_START) * self.used_budget.as_f64()
+                / CARBON_BUDGET.as_f64()
+    }
+}
+
+impl AbstractCard for Climate {
+    fn menu_string(&self) -> String {
+        "Climate".into()
+    }
+
+    fn color(&self) -> Color {
+        Color::GREEN
+    }
+
+    fn is_visible(&self) -> bool {
+        self.discovered
+    }
+}
+
+impl World {
+    pub fn climate_outcome(&self) -> Option<Outcome> {
+        self.cards.climate.outcome
+    }
+
+    /// Saved emissions count fully, even beyond what the world emits.
+    fn is_climate_stable(&self) -> bool {
+        let saved = self
+            .cards
+            .activism
+            .save_rate_from_flyers
+            .per(Duration::YEAR);
+        world_emission().per(Duration::YEAR) <= saved + NATURAL_SINK.per(Duration::YEAR)
+    }
+
+    pub(super) fn render_card_climate(&self, mut view: MutGridView<'_, Cell>) {
+        let climate = &self.cards.climate;
+        let saved = self
+            .cards
+            .activism
+            .save_rate_from_flyers
+            .per(Duration::YEAR);
+
+        view.print(0, 0, format!("Year {}", climate.year()).into());
+        view.print(
+            1,
+            0,
+            format!(
+                "World: {}/y",
+                world_emission()
+                    .per(Duration::YEAR)
+                    .stringify(1, self.notation)
+            )
+            .into(),
+        );
+        view.print(
+            2,
+            0,
+            format!(" saved: {}/y", saved.stringify(1, self.notation)).into(),
+        );
+        view.print(
+            3,
+            0,
+            format!(
+                " nature: {}/y",
+                NATURAL_SINK.per(Duration::YEAR).stringify(1, self.notation)
+            )
+            .into(),
+        );
+
+        let remaining = CARBON_BUDGET.saturating_sub(climate.used_budget);
+        view.print(
+            5,
+            0,
+            format!("Budget: {}", remaining.stringify(1, self.notation)).into(),
+        );
+        view.print(
+            6,
+            0,
+            format!("Warming: +{:.2}°C", climate.projected_warming()).into(),
+        );
+        view.print(7, 0, format!(" limit: +{WARMING_THRESHOLD:.1}°C").into());
+    }
+
+    /// Replaces the cards once the climate is decided.
+    pub(crate) fn render_end_screen(&self, outcome: Outcome, mut view: MutGridView<'_, Cell>) {
+        let climate = &self.cards.climate;
+        let mut inner = view.block();
+
+        let (title, color, summary) = match outcome {
+            Outcome::Victory => (
+                "The climate is stable!",
+                Color::GREEN,
+                "Nature takes up what is emitted.",
+            ),
+            Outcome::Failure => (
+                "The carbon budget ran out.",
+                Color::RED,
+                "Warming crossed the threshold.",
+            ),
+        };
+        inner.print(0, 0, Text::new().styled(title, Some(color), None));
+        inner.print(1, 0, summary.to_owned().into());
+        inner.print(
+            3,
+            0,
+            format!(
+                "Year {}, warming +{:.2}°C",
+                climate.year(),
+                climate.projected_warming()
+            )
+            .into(),
+        );
+        inner.print(
+            4,
+            0,
+            format!(
+                "Supporters: {}",
+                self.cards
+                    .activism
+                    .population
+                    .supporting_people()
+                    .stringify(0, self.notation)
+            )
+            .into(),
+        );
+        inner.print(6, 0, "This world is over.".to_owned().into());
+        inner.print(7, 0, "n: start over in a new world".to_owned().into());
+    }
+
+    pub(crate) fn can_start_new_game(&self) -> bool {
+        self.climate_outcome().is_some()
+    }
+
+    /// Replaces everything with a new world, bonuses included. Only the time goes on.
+    pub(crate) fn start_new_game(&mut self) {
+        assert!(self.can_start_new_game());
+        self.cards = Cards::new(self.cards.research.manager.tree().clone());
+        self.messages.queue(Message::new(
+            "A new world, a new chance.".to_owned(),
+            STANDARD_MESSAGE_DURATION,
+        ));
+    }
+
+    /// The climate waits for the prolog and stops once it is decided.
+    pub(super) fn simulate_card_climate(&mut self, delta: Duration) {
+        if self.is_in_prolog() || self.cards.climate.outcome.is_some() {
+            return;
+        }
+        self.cards.climate.discovered = true;
+
+        // Saturating, so that even absences of centuries just use up the budget.
+        let climate_delta = delta.saturating_mul(TIME_COMPRESSION);
+        let used = world_emission()
+            .saturating_mul(climate_delta)
+            .saturating_sub(
+                self.cards
+                    .activism
+                    .save_rate_from_flyers
+                    .saturating_mul(climate_delta),
+            )
+            .saturating_sub(NATURAL_SINK.saturating_mul(climate_delta));
+
+        let stable = self.is_climate_stable();
+        let climate = &mut self.cards.climate;
+        climate.elapsed = climate.elapsed.saturating_add(climate_delta);
+        climate.used_budget = climate.used_budget.saturating_add(used);
+
+        let outcome = if CARBON_BUDGET <= climate.used_budget {
+            climate.used_budget = CARBON_BUDGET;
+            Outcome::Failure
+        } else if stable {
+            Outcome::Victory
+        } else {
+            return;
+        };
+        climate.outcome = Some(outcome);
+        let text = match outcome {
+            Outcome::Victory => "The climate is stable. You won!",
+            Outcome::Failure => "Warming crossed the threshold. You lost.",
+        };
+        self.messages
+            .queue(Message::new(text.to_owned(), STANDARD_MESSAGE_DURATION));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        world::{
+            action::Action,
+            quantity::Quantity,
+            rate::{Rate, RatePerUnit},
+            World,
+        },
+    };
+
+    use super::Outcome;
+
+    #[test]
+    fn budget_runs_out_without_supporters() {
+        let mut world = World::new();
+        world.simulate(Duration::from_seconds(100 * 24 * 60 * 60));
+        assert_eq!(world.climate_outcome(), None);
+
+        world.skip_prolog();
+        world.simulate(Duration::from_seconds(200 * 24 * 60 * 60));
+        assert_eq!(world.climate_outcome(), Some(Outcome::Failure));
+        assert!(!world.apply_action(Action::PrintFlyer));
+        assert!(!world.apply_action(Action::EndCampaign));
+
+        let ticks = world.get_total_ticks();
+        assert!(world.apply_action(Action::NewGame));
+        assert_eq!(world.climate_outcome(), None);
+        assert!(world.is_in_prolog());
+        assert_eq!(world.get_total_ticks(), ticks);
+        assert!(!world.apply_action(Action::NewGame));
+    }
+
+    #[test]
+    fn saving_enough_stabilises_the_climate() {
+        let mut world = World::new_after_prolog();
+        world.simulate(Duration::from_seconds(24 * 60 * 60));
+        assert_eq!(world.climate_outcome(), None);
+
+        // Even after a day of lapsing supporters, enough are left.
+        for region in &mut world.cards.activism.population.regions {
+            region.convince(region.unsupporting_people);
+        }
+        world.cards.activism.flyer_effectiveness =
+            RatePerUnit::new(Rate::new(Quantity::new(10_000_000), Duration::YEAR));
+        world.simulate(Duration::from_seconds(24 * 60 * 60 + 1));
+        assert_eq!(world.climate_outcome(), Some(Outcome::Victory));
+    }
+
+    #[test]
+    fn ending_a_campaign_keeps_the_climate() {
+        let mut world = World::new_after_prolog();
+        world.cards.activism.total_number_of_flyers = Quantity::new(3_000);
+        world.simulate(Duration::from_seconds(24 * 60 * 60));
+        let used_budget = world.cards.climate.used_budget;
+        assert!(used_budget != Quantity::default());
+
+        assert!(world.apply_action(Action::EndCampaign));
+        assert_eq!(world.cards.climate.used_budget, used_budget);
+    }
+}
@@ -0,0 +1,302 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    duration::Duration,
+    grid::{text::Text, Cell, Color, MutGridView},
+    world::{
+        message::{Message, STANDARD_MESSAGE_DURATION},
+        quantity::{
+            types::{Money, Person},
+            Quantity,
+        },
+        rate::{Rate, RatePerUnit},
+        World,
+    },
+};
+
+use super::abstract_card::AbstractCard;
+
+const DAY: Duration = Duration::from_seconds(24 * 60 * 60);
+const INITIAL_MONEY: Quantity<Money> = Quantity::new(50);
+const DONATION_PER_SUPPORTER: RatePerUnit<Money, Person> =
+    RatePerUnit::new(Rate::new(Quantity::new(1), DAY));
+const SALARY: RatePerUnit<Money, Person> = RatePerUnit::new(Rate::new(Quantity::new(5), DAY));
+/// Paper and ink of one flyer, printed by hand or by a printer.
+pub const FLYER_PRINT_PRICE: Quantity<Money> = Quantity::fraction(1, 100);
+/// Materials of the active research project, for every researcher working on it.
+const PROJECT_SPENDING_PER_RESEARCHER: RatePerUnit<Money, Person> =
+    RatePerUnit::new(Rate::new(Quantity::new(2), DAY));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Funding {
+    discovered: bool,
+    pub money: Quantity<Money>,
+    /// Whether the last salaries were paid, unpaid staff does not work.
+    staff_is_paid: bool,
+    /// The part of the current time step the staff is paid for and works.
+    #[serde(skip)]
+    paid_time: Duration,
+}
+
+impl Funding {
+    pub fn new() -> Funding {
+        Self {
+            discovered: false,
+            money: INITIAL_MONEY,
+            staff_is_paid: true,
+            paid_time: Duration::INSTANT,
+        }
+    }
+
+    /// Pays `cost` for as much of `delta` as the money covers, returns the part that was paid.
+    fn pay_for(&mut self, cost: Rate<Money>, delta: Duration) -> Duration {
+        let total = cost.saturating_mul(delta);
+        if self.money.try_pay(total) {
+            return delta;
+        }
+        let affordable = self.money.as_f64() / total.as_f64();
+        self.money = Quantity::default();
+        (delta.ticks() as f64 * affordable) as u128 * Duration::TICK
+    }
+}
+
+impl AbstractCard for Funding {
+    fn menu_string(&self) -> String {
+        "Funding".into()
+    }
+
+    fn color(&self) -> Color {
+        Color::YELLOW
+    }
+
+    fn is_visible(&self) -> bool {
+        self.discovered
+    }
+}
+
+impl World {
+    fn donation_rate(&self) -> Rate<Money> {
+        DONATION_PER_SUPPORTER * self.cards.activism.population.supporting_people()
+    }
+
+    fn salaries(&self) -> Rate<Money> {
+        let staff = &self.cards.staff;
+        SALARY * (staff.researcher + staff.activists)
+    }
+
+    fn project_spending(&self) -> Rate<Money> {
+        PROJECT_SPENDING_PER_RESEARCHER * self.cards.staff.researcher
+    }
+
+    pub(super) fn is_staff_paid(&self) -> bool {
+        self.cards.funding.staff_is_paid
+    }
+
+    /// How much of `delta`, the current time step, the staff works.
+    pub(super) fn paid_time(&self, delta: Duration) -> Duration {
+        let paid_time = self.cards.funding.paid_time;
+        if paid_time < delta {
+            paid_time
+        } else {
+            delta
+        }
+    }
+
+    pub(super) fn can_pay_money(&self, cost: Quantity<Money>) -> bool {
+        cost <= self.cards.funding.money
+    }
+
+    pub(super) fn pay_money(&mut self, cost: Quantity<Money>) {
+        assert!(self.cards.funding.money.try_pay(cost));
+    }
+
+    /// Pays for the active research project during `delta`, returns how much of it was paid.
+    pub(super) fn pay_project(&mut self, delta: Duration) -> Duration {
+        let cost = self.project_spending();
+        self.cards.funding.pay_for(cost, delta)
+    }
+
+    pub(super) fn render_card_funding(&self, mut view: MutGridView<'_, Cell>) {
+        view.print(
+            0,
+            0,
+            format!(
+                "Money: {}",
+                self.cards.funding.money.stringify(2, self.notation)
+            )
+            .into(),
+        );
+        view.print(
+            2,
+            0,
+            format!(
+                "Donations: +{}/d",
+                self.donation_rate().per(DAY).stringify(2, self.notation)
+            )
+            .into(),
+        );
+        view.print(
+            3,
+            0,
+            format!(
+                "Salaries: -{}/d",
+                self.salaries().per(DAY).stringify(2, self.notation)
+            )
+            .into(),
+        );
+        if self.cards.research.manager.active().is_some() {
+            view.print(
+                4,
+                0,
+                format!(
+                    "Project: -{}/d",
+                    self.project_spending().per(DAY).stringify(2, self.notation)
+                )
+                .into(),
+            );
+        }
+        view.print(
+            6,
+            0,
+            format!("Flyer: {}", FLYER_PRINT_PRICE.stringify(2, self.notation)).into(),
+        );
+
+        if !self.is_staff_paid() {
+            view.print(
+                8,
+                0,
+                Text::new().styled("Staff is unpaid", Some(Color::RED), None),
+            );
+        }
+    }
+
+    /// Salaries that cannot be paid for the whole time step are paid for the part that money and
+    /// donations cover, as if staff stopped working once the money ran out.
+    pub(super) fn simulate_card_funding(&mut self, delta: Duration) {
+        if !self.is_in_prolog() {
+            self.cards.funding.discovered = true;
+        }
+
+        let donations = self.donation_rate().saturating_mul(delta);
+        let salaries = self.salaries();
+        let funding = &mut self.cards.funding;
+        funding.money = funding.money.saturating_add(donations);
+
+        let was_paid = funding.staff_is_paid;
+        funding.paid_time = funding.pay_for(salaries, delta);
+        funding.staff_is_paid = funding.paid_time == delta;
+        if was_paid && !funding.staff_is_paid {
+            self.messages.queue(Message::new(
+                "Your staff stopped working, salaries are unpaid.".to_owned(),
+                STANDARD_MESSAGE_DURATION,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        world::{
+            action::Action,
+            cards::research::Trigger,
+            quantity::{format::Notation, Quantity},
+            World,
+        },
+    };
+
+    use super::{PROJECT_SPENDING_PER_RESEARCHER, SALARY};
+
+    #[test]
+    fn each_world_writes_money_in_its_own_notation() {
+        let mut units = World::new();
+        units.cards.funding.money = Quantity::new(12_345);
+        let mut scientific = units.clone();
+        scientific.set_notation(Notation::Scientific);
+
+        assert!(units.describe().contains(&"Money: $12,345.00".to_owned()));
+        assert!(scientific.describe().contains(&"Money: $1.23e4".to_owned()));
+    }
+
+    #[test]
+    fn unpaid_staff_stops_working() {
+        let mut world = World::new_after_prolog();
+        world.cards.activism.flyer = Quantity::new(1_000);
+        world.cards.staff.activists = Quantity::new(1);
+        world.cards.funding.money = Quantity::default();
+
+        world.simulate(Duration::from_seconds(60 * 60));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(1_000));
+        assert!(!world.is_staff_paid());
+
+        // An hour costs a bit more than 20 cents.
+        world.cards.funding.money = Quantity::fraction(1, 4);
+        world.simulate(Duration::from_seconds(2 * 60 * 60));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(1_000 - 360));
+        assert!(world.is_staff_paid());
+    }
+
+    #[test]
+    fn salaries_are_paid_for_the_affordable_part() {
+        let mut world = World::new_after_prolog();
+        world.cards.activism.flyer = Quantity::new(1_000);
+        world.cards.staff.activists = Quantity::new(1);
+        // 10 cents pay one activist for 28.8 minutes, enough for 172.8 flyers.
+        world.cards.funding.money = Quantity::fraction(1, 10);
+
+        world.simulate(Duration::from_seconds(60 * 60));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(1_000 - 172));
+        assert_eq!(world.cards.funding.money, Quantity::default());
+        assert!(!world.is_staff_paid());
+    }
+
+    #[test]
+    fn printing_needs_money() {
+        let mut world = World::new_after_prolog();
+        world.cards.activism.flyer = Quantity::default();
+        world.cards.activism.maximal_emission_deficit = Quantity::new(1_000);
+        world.cards.funding.money = Quantity::fraction(1, 100);
+
+        assert!(world.apply_action(Action::PrintFlyer));
+        assert!(world.apply_action(Action::PrintFlyer));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(1));
+        assert_eq!(world.cards.funding.money, Quantity::default());
+    }
+
+    #[test]
+    fn projects_are_paid_for_the_time_they_are_worked_on() {
+        let mut world = World::new();
+        world.cards.staff.researcher = Quantity::new(1);
+        world
+            .cards
+            .research
+            .manager
+            .trigger(Trigger::ManualResearch);
+        world.simulate(Duration::TICK);
+        let project = world.cards.research.manager.available()[0];
+        assert!(world.apply_action(Action::StartResearch(project)));
+        let salaries = SALARY * Quantity::new(1);
+        let spending = PROJECT_SPENDING_PER_RESEARCHER * Quantity::new(1);
+
+        // The project needs 30s, but the money only covers the materials for 15s of them.
+        world.cards.funding.money =
+            (salaries * Duration::MINUTE).saturating_add(spending * Duration::from_seconds(15));
+        world.simulate(world.get_total_ticks() + Duration::MINUTE);
+        assert!(world.is_staff_paid());
+        assert_eq!(world.cards.funding.money, Quantity::default());
+        let &(_, progress) = world.cards.research.manager.active().unwrap();
+        let expected = world.research_rate() * Duration::from_seconds(15);
+        assert!((progress.as_f64() / expected.as_f64() - 1.0).abs() < 1e-6);
+
+        // The project is finished after another 15s, the rest of the minute costs no materials.
+        let money = Quantity::new(100);
+        world.cards.funding.money = money;
+        world.simulate(world.get_total_ticks() + Duration::MINUTE);
+        assert!(world.cards.research.manager.active().is_none());
+        let spent = money.saturating_sub(world.cards.funding.money);
+        let expected =
+            (salaries * Duration::MINUTE).saturating_add(spending * Duration::from_seconds(15));
+        assert!((spent.as_f64() / expected.as_f64() - 1.0).abs() < 1e-6);
+    }
+}
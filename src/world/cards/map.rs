@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    duration::Duration,
+    grid::{text::Text, Cell, Color, MutGridView},
+    input::Command,
+    world::{action::Action, World},
+};
+
+use super::{
+    abstract_card::AbstractCard,
+    activism::regions::{Region, REGIONS},
+};
+
+/// Every letter is the symbol of the region it belongs to.
+const MAP: [&str; 7] = [
+    " NNNNNN    EEE AAAAAAA",
+    "NNNNNNN   EEEEAAAAAAAAA",
+    "  NNNN     EE AAAAAAAA",
+    "    LL    FFFF AAAA",
+    "     LLL  FFFFF  A  O",
+    "     LLL   FFF     OOO",
+    "      L     F       O",
+];
+
+fn support_color(region: &Region) -> Color {
+    match region.support_share() {
+        share if share < 0.01 => Color::GREY,
+        share if share < 0.1 => Color::ORANGE,
+        share if share < 0.5 => Color::YELLOW,
+        _ => Color::GREEN,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldMap {
+    discovered: bool,
+}
+
+impl WorldMap {
+    pub fn new() -> WorldMap {
+        Self { discovered: false }
+    }
+}
+
+impl AbstractCard for WorldMap {
+    fn menu_string(&self) -> String {
+        "Map".into()
+    }
+
+    fn color(&self) -> Color {
+        Color::BLUE
+    }
+
+    fn is_visible(&self) -> bool {
+        self.discovered
+    }
+}
+
+impl World {
+    pub(super) fn render_card_map(&self, mut view: MutGridView<'_, Cell>) {
+        let population = &self.cards.activism.population;
+
+        for (line, row) in MAP.iter().enumerate() {
+            for (char_id, symbol) in row.chars().enumerate() {
+                let Some(region_id) = REGIONS.iter().position(|r| r.symbol == symbol) else {
+                    continue;
+                };
+                let color = support_color(&population.regions[region_id]);
+                let (foreground, background) = if region_id == population.flyer_target {
+                    (Color::BLACK, Some(color))
+                } else {
+                    (color, None)
+                };
+                view.print(
+                    line,
+                    char_id,
+                    Text::new().styled(&symbol.to_string(), Some(foreground), background),
+                );
+            }
+        }
+
+        let target = population.flyer_target;
+        let definition = &REGIONS[target];
+        view.print(
+            7,
+            0,
+            format!(
+                "{} {:.1}%",
+                definition.name,
+                100.0 * population.regions[target].support_share()
+            )
+            .into(),
+        );
+        let (numerator, denominator) = definition.persuasion;
+        view.print(
+            8,
+            0,
+            format!(
+                " x{} flyers, {}t/y",
+                numerator as f64 / denominator as f64,
+                definition.per_capita_tonnes
+            )
+            .into(),
+        );
+        view.print(9, 0, "←/→: flyer target".to_owned().into());
+    }
+
+    pub(super) fn map_action_for_command(&self, command: Command) -> Option<Action> {
+        let target = self.cards.activism.population.flyer_target;
+        match command {
+            Command::Left => Some(Action::TargetRegion(target.checked_sub(1)?)),
+            Command::Right => Some(Action::TargetRegion(target + 1)),
+            Command::Choose(number) => Some(Action::TargetRegion(number.checked_sub(1)?)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn can_target_region(&self, region: usize) -> bool {
+        self.cards.map.discovered
+            && region < REGIONS.len()
+            && region != self.cards.activism.population.flyer_target
+    }
+
+    pub(crate) fn target_region(&mut self, region: usize) {
+        assert!(self.can_target_region(region));
+        self.cards.activism.population.flyer_target = region;
+    }
+
+    pub(super) fn simulate_card_map(&mut self, _delta: Duration) {
+        if !self.is_in_prolog() {
+            self.cards.map.discovered = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        world::{action::Action, quantity::Quantity, World},
+    };
+
+    #[test]
+    fn flyers_convince_in_the_target_region() {
+        let mut world = World::new_after_prolog();
+        assert!(!world.apply_action(Action::TargetRegion(3)));
+        world.simulate(Duration::SECOND);
+
+        // Africa is easier to convince.
+        assert!(world.apply_action(Action::TargetRegion(3)));
+        assert!(!world.apply_action(Action::TargetRegion(6)));
+        world.apply_action(Action::HandoutFlyer);
+        let regions = &world.cards.activism.population.regions;
+        assert_eq!(regions[3].supporting_people, Quantity::fraction(3, 20));
+        assert_eq!(regions[2].supporting_people, Quantity::default());
+    }
+}
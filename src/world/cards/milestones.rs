@@ -1,20 +1,121 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
     duration::Duration,
-    grid::{Cell, Color, MutGridView},
-    input::Input,
-    world::World,
+    grid::{text::Text, Cell, Color, MutGridView},
+    world::{
+        message::{Message, STANDARD_MESSAGE_DURATION},
+        quantity::{
+            format::Notation,
+            types::{Emission, Flyer, Person},
+            Quantity,
+        },
+        World,
+    },
 };
 
 use super::abstract_card::AbstractCard;
 
-#[derive(Debug)]
+enum Goal {
+    SavedEmission(Quantity<Emission>),
+    Supporters(Quantity<Person>),
+    TotalFlyers(Quantity<Flyer>),
+    FinishedResearch(usize),
+}
+
+enum Reward {
+    MaximalEmissionDeficit(Quantity<Emission>),
+    Researchers(u128),
+    FlyerPersuasiveness(Quantity<Person>),
+}
+
+impl Reward {
+    /// At most 15 characters.
+    fn describe(&self, notation: Notation) -> String {
+        match self {
+            Reward::MaximalEmissionDeficit(increase) => {
+                format!("Deficit +{}", increase.stringify(0, notation))
+            }
+            Reward::Researchers(1) => "+1 researcher".to_owned(),
+            Reward::Researchers(researchers) => format!("+{researchers} researchers"),
+            Reward::FlyerPersuasiveness(increase) => {
+                format!("Convince +{}", increase.stringify(2, notation))
+            }
+        }
+    }
+}
+
+struct Milestone {
+    name: &'static str,
+    goal: Goal,
+    reward: Reward,
+}
+
+/// Names have at most 15 characters, so they fit next to the progress.
+/// The first supporter is won in the prolog. Its reward makes the flyer printed at the prolog's
+/// end convince more, but must leave the emission deficit alone, which the printing steps rely on.
+const MILESTONES: [Milestone; 9] = [
+    Milestone {
+        name: "First supporter",
+        goal: Goal::Supporters(Quantity::new(1)),
+        reward: Reward::FlyerPersuasiveness(Quantity::fraction(1, 20)),
+    },
+    Milestone {
+        name: "One kilogram",
+        goal: Goal::SavedEmission(Quantity::new(1_000)),
+        reward: Reward::Researchers(1),
+    },
+    Milestone {
+        name: "Printing press",
+        goal: Goal::TotalFlyers(Quantity::new(100)),
+        reward: Reward::MaximalEmissionDeficit(Quantity::new(2_000)),
+    },
+    Milestone {
+        name: "Ten supporters",
+        goal: Goal::Supporters(Quantity::new(10)),
+        reward: Reward::MaximalEmissionDeficit(Quantity::new(5_000)),
+    },
+    Milestone {
+        name: "First discovery",
+        goal: Goal::FinishedResearch(1),
+        reward: Reward::Researchers(1),
+    },
+    Milestone {
+        name: "One ton",
+        goal: Goal::SavedEmission(Quantity::new(1_000_000)),
+        reward: Reward::MaximalEmissionDeficit(Quantity::new(10_000)),
+    },
+    Milestone {
+        name: "Crowd",
+        goal: Goal::Supporters(Quantity::new(100)),
+        reward: Reward::Researchers(2),
+    },
+    Milestone {
+        name: "Flyer flood",
+        goal: Goal::TotalFlyers(Quantity::new(1_000)),
+        reward: Reward::FlyerPersuasiveness(Quantity::fraction(1, 20)),
+    },
+    Milestone {
+        name: "Research lab",
+        goal: Goal::FinishedResearch(4),
+        reward: Reward::MaximalEmissionDeficit(Quantity::new(50_000)),
+    },
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Milestones {
     is_visible: bool,
+    /// Indices into [`MILESTONES`] whose reward was granted.
+    #[serde(default)]
+    reached: Vec<usize>,
 }
 
 impl Milestones {
     pub fn new() -> Milestones {
-        Self { is_visible: false }
+        Self {
+            is_visible: false,
+            reached: Vec::new(),
+        }
     }
 
     pub fn discover(&mut self) {
@@ -33,17 +134,133 @@ impl AbstractCard for Milestones {
     }
 
     fn is_visible(&self) -> bool {
-        false
+        self.is_visible
     }
 }
 
 impl World {
-    pub(super) fn render_card_milestones(
-        &mut self,
-        _input: &Input,
-        mut _view: MutGridView<'_, Cell>,
-    ) {
+    /// How far the goal is reached, between 0 and 1.
+    fn goal_progress(&self, goal: &Goal) -> f64 {
+        let (current, target) = match goal {
+            Goal::SavedEmission(target) => (self.cards.saved_emission().as_f64(), target.as_f64()),
+            Goal::Supporters(target) => (
+                self.cards.activism.population.supporting_people().as_f64(),
+                target.as_f64(),
+            ),
+            Goal::TotalFlyers(target) => (
+                self.cards.activism.total_number_of_flyers.as_f64(),
+                target.as_f64(),
+            ),
+            Goal::FinishedResearch(target) => {
+                (self.cards.finished_research().len() as f64, *target as f64)
+            }
+        };
+        (current / target).min(1.0)
     }
 
-    pub(super) fn simulate_card_milestones(&mut self, _delta: Duration) {}
+    fn is_goal_reached(&self, goal: &Goal) -> bool {
+        match goal {
+            Goal::SavedEmission(target) => &self.cards.saved_emission() >= target,
+            Goal::Supporters(target) => {
+                &self.cards.activism.population.supporting_people() >= target
+            }
+            Goal::TotalFlyers(target) => &self.cards.activism.total_number_of_flyers >= target,
+            Goal::FinishedResearch(target) => &self.cards.finished_research().len() >= target,
+        }
+    }
+
+    fn grant_reward(&mut self, reward: &Reward) {
+        match reward {
+            Reward::MaximalEmissionDeficit(increase) => {
+                let new_maximal_deficit = self.cards.activism.maximal_emission_deficit + *increase;
+                self.set_maximal_emission_deficit(new_maximal_deficit);
+            }
+            Reward::Researchers(researchers) => self.cards.staff.researcher += *researchers,
+            Reward::FlyerPersuasiveness(increase) => {
+                self.cards.activism.flyer_persuasiveness_bonus += *increase
+            }
+        }
+    }
+
+    pub(super) fn render_card_milestones(&self, mut view: MutGridView<'_, Cell>) {
+        let reached = &self.cards.milestones.reached;
+        let next = (0..MILESTONES.len()).find(|index| !reached.contains(index));
+
+        for (index, milestone) in MILESTONES.iter().enumerate() {
+            let (marker, color) = if reached.contains(&index) {
+                ('√', Some(Color::GREEN))
+            } else if Some(index) == next {
+                ('>', None)
+            } else {
+                (' ', None)
+            };
+            let percent = (100.0 * self.goal_progress(&milestone.goal)).floor();
+            view.print(
+                index,
+                0,
+                Text::new().styled(
+                    &format!("{} {:<15}{:>4}%", marker, milestone.name, percent),
+                    color,
+                    None,
+                ),
+            );
+        }
+
+        if let Some(next) = next {
+            view.print(
+                view.height() - 1,
+                0,
+                format!(
+                    "Reward: {}",
+                    MILESTONES[next].reward.describe(self.notation)
+                )
+                .into(),
+            );
+        }
+    }
+
+    /// Every reward is granted once, even if the goal is reached before the card is visible.
+    pub(super) fn simulate_card_milestones(&mut self, _delta: Duration) {
+        for (index, milestone) in MILESTONES.iter().enumerate() {
+            if self.cards.milestones.reached.contains(&index)
+                || !self.is_goal_reached(&milestone.goal)
+            {
+                continue;
+            }
+
+            self.cards.milestones.reached.push(index);
+            self.grant_reward(&milestone.reward);
+            self.messages.queue(Message::new(
+                format!("Milestone reached: {}", milestone.name),
+                STANDARD_MESSAGE_DURATION,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        world::{quantity::Quantity, World},
+    };
+
+    #[test]
+    fn rewards_are_granted_once() {
+        let mut world = World::new();
+        world.cards.activism.total_number_of_flyers = Quantity::new(100);
+        world.cards.activism.maximal_emission_deficit = Quantity::new(1_000);
+
+        world.simulate(Duration::SECOND);
+        assert_eq!(
+            world.cards.activism.maximal_emission_deficit,
+            Quantity::new(3_000)
+        );
+
+        world.simulate(Duration::MINUTE);
+        assert_eq!(
+            world.cards.activism.maximal_emission_deficit,
+            Quantity::new(3_000)
+        );
+    }
 }
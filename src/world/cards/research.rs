@@ -1,105 +1,62 @@
-use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    duration::Duration,
+    duration::{stringify_estimate, Duration, Style},
     grid::{text::Text, Cell, Color, MutGridView},
-    input::{Event, Input, Key},
+    input::Command,
     world::{
+        action::Action,
         message::{Message, STANDARD_MESSAGE_DURATION},
         quantity::{
             types::{Emission, Person, ResearchPoints},
             Quantity,
         },
-        rate::Rate,
+        rate::{Rate, RatePerUnit},
         World,
     },
 };
 
 use self::research_manager::ResearchManager;
+pub use self::tree::{Effect, Project, ProjectDefinition, ResearchTree, Trigger};
 
-use super::{abstract_card::AbstractCard, activism::INITIAL_FLYER_PERSUASIVENESS};
+use super::abstract_card::AbstractCard;
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-pub enum Project {
-    BetterGuidelines1,
-    BetterGuidelines2,
-    CatchierFlyer1,
-    Recycling,
-}
+pub mod tree;
+mod tree_view;
 
-const ALL_PROJECTS: [Project; 4] = [
-    Project::BetterGuidelines1,
-    Project::BetterGuidelines2,
-    Project::CatchierFlyer1,
-    Project::Recycling,
-];
-
-pub const FLYER_EFFECTIVENESS_0: Rate<Emission> = Rate::new(Quantity::new(100_000), Duration::YEAR);
-const FLYER_EFFECTIVENESS_1: Rate<Emission> = Rate::new(Quantity::new(150_000), Duration::YEAR);
-const FLYER_EFFECTIVENESS_2: Rate<Emission> = Rate::new(Quantity::new(500_000), Duration::YEAR);
-
-const CATCHIER_FLYER_0: Quantity<Person> = INITIAL_FLYER_PERSUASIVENESS;
-const CATCHIER_FLYER_1: Quantity<Person> = Quantity::fraction(1, 7);
-
-impl Project {
-    fn apply(self, world: &mut World) {
-        match self {
-            Project::BetterGuidelines1 => {
-                let effectiveness = &mut world.cards.activism.flyer_effectiveness;
-                assert_eq!(effectiveness, &FLYER_EFFECTIVENESS_0);
-                *effectiveness = FLYER_EFFECTIVENESS_1;
-
-                world
-                    .cards
-                    .research
-                    .manager
-                    .unlock(Project::BetterGuidelines2);
-                world.cards.research.manager.unlock(Project::CatchierFlyer1);
-            }
-            Project::BetterGuidelines2 => {
-                let effectiveness = &mut world.cards.activism.flyer_effectiveness;
-                assert_eq!(effectiveness, &FLYER_EFFECTIVENESS_1);
-                *effectiveness = FLYER_EFFECTIVENESS_2;
-            }
-            Project::CatchierFlyer1 => {
-                let persuasiveness = &mut world.cards.activism.flyer_persuasiveness;
-                assert_eq!(persuasiveness, &CATCHIER_FLYER_0);
-                *persuasiveness = CATCHIER_FLYER_1;
-
-                world
-                    .cards
-                    .research
-                    .manager
-                    .unlock(Project::BetterGuidelines1);
+pub const FLYER_EFFECTIVENESS_0: RatePerUnit<Emission, Person> =
+    RatePerUnit::new(Rate::new(Quantity::new(100_000), Duration::YEAR));
+
+impl World {
+    fn apply_effect(&mut self, effect: Effect) {
+        let activism = &mut self.cards.activism;
+        match effect {
+            Effect::FlyerEffectiveness(effectiveness) => {
+                activism.flyer_effectiveness = RatePerUnit::new(effectiveness);
+                activism.update_save_rate();
             }
-            Project::Recycling => {
-                assert!(!world.cards.activism.has_recycling);
-                world.cards.activism.has_recycling = true;
+            Effect::FlyerPersuasiveness(persuasiveness) => {
+                activism.flyer_persuasiveness = persuasiveness
             }
+            Effect::MaximalFlyer(maximal_flyer) => activism.maximal_flyer = maximal_flyer,
+            Effect::SupporterLifetime(lifetime) => activism.opinion.supporter_lifetime = lifetime,
+            Effect::Recycling => activism.has_recycling = true,
         }
     }
+}
 
-    fn cost(&self) -> Quantity<ResearchPoints> {
-        match self {
-            Project::BetterGuidelines1 => Quantity::new(1),
-            Project::BetterGuidelines2 => Quantity::new(2),
-            Project::CatchierFlyer1 => Quantity::fraction(1, 2),
-            Project::Recycling => Quantity::fraction(3, 2),
-        }
-    }
-
-    fn name(&self) -> String {
-        match self {
-            Project::BetterGuidelines1 => "Better Guidelines".into(),
-            Project::BetterGuidelines2 => "Even better Guidelines".into(),
-            Project::CatchierFlyer1 => "Catchier Flyer".into(),
-            Project::Recycling => "Recycling".into(),
-        }
-    }
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ProjectStatus {
+    Finished,
+    Active,
+    /// Also covers queued and paused projects.
+    Available,
+    Locked,
 }
 
 mod research_manager {
-    use indexmap::IndexSet;
+    use indexmap::{IndexMap, IndexSet};
+    use serde::{Deserialize, Serialize};
 
     use crate::{
         duration::Duration,
@@ -110,97 +67,287 @@ mod research_manager {
         },
     };
 
-    use super::{Project, ALL_PROJECTS};
+    use super::{Project, ProjectDefinition, ProjectStatus, ResearchTree, Trigger};
 
+    #[derive(Clone, Serialize, Deserialize)]
     pub struct ResearchManager {
+        tree: ResearchTree,
+        triggered: IndexSet<Trigger>,
         locked: IndexSet<Project>,
         active: Option<(Project, Quantity<ResearchPoints>)>,
+        /// Projects to research after the active one, in order.
+        #[serde(default)]
+        queue: Vec<Project>,
         available: IndexSet<Project>,
+        /// Progress of projects that were worked on, but are not active anymore.
+        #[serde(default)]
+        paused: IndexMap<Project, Quantity<ResearchPoints>>,
         finished: IndexSet<Project>,
     }
 
     impl ResearchManager {
-        pub fn unlock(&mut self, project: Project) {
-            if self.locked.remove(&project) {
+        pub fn trigger(&mut self, trigger: Trigger) {
+            if self.triggered.insert(trigger) {
+                self.unlock_available();
+            }
+        }
+
+        /// Makes every locked project available whose prerequisites are met.
+        fn unlock_available(&mut self) {
+            let unlocked: Vec<_> = self
+                .locked
+                .iter()
+                .copied()
+                .filter(|&project| {
+                    let definition = self.tree.definition(project);
+                    definition
+                        .requires
+                        .iter()
+                        .all(|prerequisite| self.finished.contains(prerequisite))
+                        && definition
+                            .unlocked_by
+                            .is_none_or(|trigger| self.triggered.contains(&trigger))
+                })
+                .collect();
+
+            for project in unlocked {
+                self.locked.shift_remove(&project);
                 let success = self.available.insert(project);
                 assert!(success);
             }
         }
 
-        pub fn new() -> ResearchManager {
-            let initial = Project::CatchierFlyer1;
-            let mut locked: IndexSet<_> = ALL_PROJECTS.into();
-            let success = locked.remove(&initial);
-            assert!(success);
-            Self {
-                locked,
+        pub fn new(tree: ResearchTree) -> ResearchManager {
+            let mut manager = Self {
+                locked: tree.projects().collect(),
+                tree,
+                triggered: Default::default(),
                 active: None,
-                available: [initial].into(),
+                queue: Vec::new(),
+                available: Default::default(),
+                paused: Default::default(),
                 finished: Default::default(),
+            };
+            manager.unlock_available();
+            manager
+        }
+
+        pub fn definition(&self, project: Project) -> &ProjectDefinition {
+            self.tree.definition(project)
+        }
+
+        pub fn tree(&self) -> &ResearchTree {
+            &self.tree
+        }
+
+        pub fn status(&self, project: Project) -> ProjectStatus {
+            if self.finished.contains(&project) {
+                ProjectStatus::Finished
+            } else if self.active.is_some_and(|(active, _)| active == project) {
+                ProjectStatus::Active
+            } else if self.locked.contains(&project) {
+                ProjectStatus::Locked
+            } else {
+                ProjectStatus::Available
             }
         }
 
+        pub fn finished(&self) -> &IndexSet<Project> {
+            &self.finished
+        }
+
         pub fn available(&self) -> &IndexSet<Project> {
             &self.available
         }
 
-        pub fn active(&mut self) -> &mut Option<(Project, Quantity<ResearchPoints>)> {
-            &mut self.active
+        pub fn active(&self) -> Option<&(Project, Quantity<ResearchPoints>)> {
+            self.active.as_ref()
+        }
+
+        pub fn active_mut(&mut self) -> Option<&mut (Project, Quantity<ResearchPoints>)> {
+            self.active.as_mut()
+        }
+
+        /// Progress so far, also for paused projects.
+        pub fn progress(&self, project: Project) -> Quantity<ResearchPoints> {
+            match self.active {
+                Some((active, progress)) if active == project => progress,
+                _ => self.paused.get(&project).copied().unwrap_or_default(),
+            }
         }
 
+        /// Starts an available or queued project, continuing where it was paused.
         pub fn activate(&mut self, project: Project) {
             assert!(self.active.is_none());
-            let success = self.available.remove(&project);
+            if let Some(position) = self.queue.iter().position(|&p| p == project) {
+                self.queue.remove(position);
+            } else {
+                let success = self.available.shift_remove(&project);
+                assert!(success);
+            }
+            let progress = self.paused.shift_remove(&project).unwrap_or_default();
+            self.active = Some((project, progress));
+        }
+
+        /// Makes the active project available again, keeping its progress.
+        fn deactivate(&mut self) {
+            let (project, progress) = self.active.take().unwrap();
+            if progress != Quantity::default() {
+                self.paused.insert(project, progress);
+            }
+            let success = self.available.insert(project);
+            assert!(success);
+        }
+
+        /// The next queued project becomes active instead of the paused one.
+        pub fn pause(&mut self) {
+            self.deactivate();
+            if !self.queue.is_empty() {
+                self.activate(self.queue[0]);
+            }
+        }
+
+        /// Starts `project` right away, pausing the active one.
+        pub fn switch_to(&mut self, project: Project) {
+            if self.active.is_some() {
+                self.deactivate();
+            }
+            self.activate(project);
+        }
+
+        pub fn queue(&self) -> &[Project] {
+            &self.queue
+        }
+
+        pub fn enqueue(&mut self, project: Project) {
+            assert!(self.active.is_some());
+            let success = self.available.shift_remove(&project);
+            assert!(success);
+            self.queue.push(project);
+        }
+
+        pub fn dequeue(&mut self, project: Project) {
+            let position = self.queue.iter().position(|&p| p == project).unwrap();
+            self.queue.remove(position);
+            let success = self.available.insert(project);
+            assert!(success);
+        }
+
+        pub fn move_queued(&mut self, project: Project, to: usize) {
+            assert!(to < self.queue.len());
+            let position = self.queue.iter().position(|&p| p == project).unwrap();
+            self.queue.remove(position);
+            self.queue.insert(to, project);
+        }
+
+        /// Finishes the active project if it has enough progress.
+        /// The surplus goes into the next queued project, which becomes active.
+        fn finish_active(&mut self) -> Option<Project> {
+            let (project, progress) = self.active?;
+            let cost = self.tree.definition(project).cost;
+            if progress < cost {
+                return None;
+            }
+
+            let success = self.finished.insert(project);
             assert!(success);
-            self.active = Some((project, Quantity::default()));
+            self.active = None;
+            if !self.queue.is_empty() {
+                self.activate(self.queue[0]);
+                let (_, next_progress) = self.active.as_mut().unwrap();
+                *next_progress += progress - cost;
+            }
+            self.unlock_available();
+            Some(project)
         }
     }
     impl World {
-        pub fn simulate_research_manager(&mut self, delta: Duration) {
-            let rate = self.research_rate();
-            let done_project =
-                if let Some((project, progress)) = &mut self.cards.research.manager.active {
-                    *progress += rate * delta;
-                    if progress >= &mut project.cost() {
-                        Some(*project)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-            if let Some(project) = done_project {
-                project.apply(self);
-                let success = self.cards.research.manager.finished.insert(project);
-                assert!(success);
-                self.cards.research.manager.active = None;
+        /// Applies every project that got enough progress, possibly several queued ones at once.
+        pub fn finish_research(&mut self) {
+            while let Some(project) = self.cards.research.manager.finish_active() {
+                let definition = self.cards.research.manager.definition(project).clone();
+                for &effect in &definition.effects {
+                    self.apply_effect(effect);
+                }
 
                 self.messages.queue(Message::new(
-                    format!("Finished research: {}", project.name()),
+                    format!("Finished research: {}", definition.name),
                     STANDARD_MESSAGE_DURATION,
                 ))
             }
         }
+
+        /// Researchers work while they and the project are paid for. Without queued projects to
+        /// continue with, they stop once the active one is finished.
+        pub fn simulate_research_manager(&mut self, delta: Duration) {
+            let rate = self.research_rate();
+            let manager = &self.cards.research.manager;
+            let Some(&(project, progress)) = manager.active() else {
+                return;
+            };
+            let mut working_time = self.paid_time(delta);
+            if manager.queue().is_empty() {
+                let remaining = manager.definition(project).cost.saturating_sub(progress);
+                if let Some(finished_after) =
+                    Duration::from_quantity_and_rate_approximation(remaining, rate)
+                {
+                    if finished_after < working_time {
+                        working_time = finished_after;
+                    }
+                }
+            }
+
+            let paid_time = self.pay_project(working_time);
+            if let Some((_, progress)) = self.cards.research.manager.active_mut() {
+                *progress += rate * paid_time;
+            }
+            self.finish_research();
+        }
     }
 }
 
+/// What the Research card shows, switched with left and right.
+/// The tree has its own key, because it uses the arrow keys for navigation.
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ResearchPage {
+    #[default]
+    Projects,
+    Queue,
+    Tree,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Research {
     discovered: bool,
     pub manager: ResearchManager,
-    rate_per_researcher: Rate<ResearchPoints>,
+    pub(super) rate_per_researcher: RatePerUnit<ResearchPoints, Person>,
     pub manual_research_per_click: Quantity<ResearchPoints>,
+    #[serde(default)]
+    page: ResearchPage,
+    /// Index of the queued project that gets moved or removed.
+    #[serde(default)]
+    selected_queued: usize,
+    /// Project whose details the tree shows, the first one if none was chosen yet.
+    #[serde(default)]
+    highlighted: Option<Project>,
 }
 
 impl Research {
-    pub fn new() -> Research {
-        let mut initial_open = IndexSet::new();
-        initial_open.insert(Project::BetterGuidelines1);
+    fn keep_selection_in_queue(&mut self) {
+        self.selected_queued = self
+            .selected_queued
+            .min(self.manager.queue().len().saturating_sub(1));
+    }
 
+    pub fn new(tree: ResearchTree) -> Research {
         Research {
             discovered: false,
-            manager: ResearchManager::new(),
-            rate_per_researcher: Rate::new(Quantity::new(1), Duration::MINUTE),
+            manager: ResearchManager::new(tree),
+            rate_per_researcher: RatePerUnit::new(Rate::new(Quantity::new(1), Duration::MINUTE)),
             manual_research_per_click: Quantity::default(),
+            page: ResearchPage::default(),
+            selected_queued: 0,
+            highlighted: None,
         }
     }
 }
@@ -220,104 +367,297 @@ impl AbstractCard for Research {
 }
 
 impl World {
-    fn research_rate(&self) -> Rate<ResearchPoints> {
-        self.cards.staff.researcher.whole_amount() * self.cards.research.rate_per_researcher
+    pub(super) fn research_rate(&self) -> Rate<ResearchPoints> {
+        self.cards.research.rate_per_researcher * self.cards.staff.researcher
     }
 
-    fn render_inactive_not_empty(&mut self, input: &Input, mut view: MutGridView<'_, Cell>) {
-        assert!(self.cards.research.manager.active().is_none());
-        assert!(!self.cards.research.manager.available().is_empty());
-        view.print_overflowing(0, "Choose next research project.".to_owned().into());
-
-        let mut selected = None;
-        for (mut id, &project) in self.cards.research.manager.available().iter().enumerate() {
+    /// Lists the available projects from `first_line` on, numbered for choosing.
+    /// Partially researched projects are highlighted and show the remaining time.
+    fn render_available(&self, view: &mut MutGridView<'_, Cell>, first_line: usize) {
+        let manager = &self.cards.research.manager;
+        let lines = view.height() - first_line;
+        for (mut id, &project) in manager.available().iter().enumerate().take(lines) {
+            let definition = manager.definition(project);
+            let progress = manager.progress(project);
+            let color = (progress != Quantity::default()).then_some(Color::YELLOW);
             id += 1;
             view.print_overflowing(
-                id,
-                Text::new().raw(&format!(
-                    "{}: {} [{}]",
-                    id,
-                    project.name(),
-                    Duration::from_quantity_and_rate_approximation(
-                        project.cost(),
-                        self.research_rate()
-                    )
-                    .stringify(2)
-                )),
+                first_line + id - 1,
+                Text::new().styled(
+                    &format!(
+                        "{}: {} [{}]",
+                        id,
+                        definition.name,
+                        stringify_estimate(
+                            Duration::from_quantity_and_rate_approximation(
+                                definition.cost - progress,
+                                self.research_rate()
+                            ),
+                            Style::Compact
+                        )
+                    ),
+                    color,
+                    None,
+                ),
             );
-
-            let required_key = Key::number(id);
-            if let Some(Event::Key(key)) = input.event {
-                if key == required_key {
-                    selected = Some(project);
-                }
-            }
         }
+    }
 
-        if let Some(selected) = selected {
-            self.cards.research.manager.activate(selected);
-        }
+    fn render_inactive_not_empty(&self, mut view: MutGridView<'_, Cell>) {
+        assert!(self.cards.research.manager.active().is_none());
+        assert!(!self.cards.research.manager.available().is_empty());
+        view.print_overflowing(0, "Choose next research project.".to_owned().into());
+
+        self.render_available(&mut view, 1);
     }
 
-    fn render_inactive_empty(&mut self, _input: &Input, mut view: MutGridView<'_, Cell>) {
+    fn render_inactive_empty(&self, mut view: MutGridView<'_, Cell>) {
         view.print_overflowing(1, "No open research projects.".to_owned().into());
         view.print_overflowing(2, "Maybe come back later?".to_owned().into());
     }
 
-    fn render_inactive(&mut self, input: &Input, view: MutGridView<'_, Cell>) {
+    fn render_inactive(&self, view: MutGridView<'_, Cell>) {
         if self.cards.research.manager.available().is_empty() {
-            self.render_inactive_empty(input, view);
+            self.render_inactive_empty(view);
         } else {
-            self.render_inactive_not_empty(input, view);
+            self.render_inactive_not_empty(view);
         }
     }
 
-    fn render_active(&mut self, input: &Input, mut view: MutGridView<'_, Cell>) {
+    fn render_active(&self, mut view: MutGridView<'_, Cell>) {
         let rate = self.research_rate();
-        let (project, progress) = self.cards.research.manager.active().as_mut().unwrap();
+        let manager = &self.cards.research.manager;
+        let (project, progress) = manager.active().unwrap();
+        let definition = manager.definition(*project);
 
-        let dur = Duration::from_quantity_and_rate_approximation(project.cost() - *progress, rate);
+        let dur = Duration::from_quantity_and_rate_approximation(definition.cost - *progress, rate);
 
-        view.print_overflowing(0, "Current research:".to_owned().into());
-        view.print_overflowing(1, project.name().into());
+        view.print_overflowing(0, definition.name.clone().into());
         view.print(
-            3,
+            1,
             0,
             format!(
                 "Progress: {:.2}%",
-                100.0 * progress.as_f64() / project.cost().as_f64(),
+                100.0 * progress.as_f64() / definition.cost.as_f64(),
             )
             .into(),
         );
-        view.print(4, 0, format!("[{}]", dur.stringify(2)).into());
+        view.print(
+            2,
+            0,
+            format!("[{}]", stringify_estimate(dur, Style::Compact)).into(),
+        );
 
         if self.cards.research.manual_research_per_click != Quantity::default() {
             view.print_overflowing(
-                6,
+                3,
                 format!(
                     "Speed up {} with r.",
-                    Duration::from_quantity_and_rate_approximation(
-                        self.cards.research.manual_research_per_click,
-                        rate
+                    stringify_estimate(
+                        Duration::from_quantity_and_rate_approximation(
+                            self.cards.research.manual_research_per_click,
+                            rate
+                        ),
+                        Style::Compact
                     )
-                    .stringify(2)
                 )
                 .into(),
             );
-            if let Some(Event::Key(Key::R)) = input.event {
-                *progress += self.cards.research.manual_research_per_click;
+        }
+
+        if !manager.available().is_empty() {
+            view.print(
+                4,
+                0,
+                format!("Queue next ({} →):", manager.queue().len()).into(),
+            );
+            self.render_available(&mut view, 5);
+        } else if !manager.queue().is_empty() {
+            view.print(4, 0, format!("Queued: {} →", manager.queue().len()).into());
+        }
+    }
+
+    fn render_queue(&self, mut view: MutGridView<'_, Cell>) {
+        let manager = &self.cards.research.manager;
+        view.print(0, 0, "← Research queue:".to_owned().into());
+
+        if manager.queue().is_empty() {
+            view.print_overflowing(2, "Nothing planned yet.".to_owned().into());
+            return;
+        }
+
+        let lines = view.height() - 2;
+        for (index, &project) in manager.queue().iter().enumerate().take(lines) {
+            let marker = if index == self.cards.research.selected_queued {
+                '>'
+            } else {
+                ' '
+            };
+            view.print_overflowing(
+                index + 1,
+                format!(
+                    "{}{}: {}",
+                    marker,
+                    index + 1,
+                    manager.definition(project).name
+                )
+                .into(),
+            );
+        }
+
+        view.print(
+            view.height() - 1,
+            0,
+            "u/d:move x:drop s:start".to_owned().into(),
+        );
+    }
+
+    pub(super) fn render_card_research(&self, view: MutGridView<'_, Cell>) {
+        match self.cards.research.page {
+            ResearchPage::Queue => self.render_queue(view),
+            ResearchPage::Tree => self.render_research_tree(view),
+            ResearchPage::Projects if self.cards.research.manager.active().is_some() => {
+                self.render_active(view)
             }
+            ResearchPage::Projects => self.render_inactive(view),
         }
     }
 
-    pub(super) fn render_card_research(&mut self, input: &Input, view: MutGridView<'_, Cell>) {
-        if self.cards.research.manager.active().is_some() {
-            self.render_active(input, view);
-        } else {
-            self.render_inactive(input, view);
+    pub(super) fn research_uses_arrow_keys(&self) -> bool {
+        self.cards.research.page == ResearchPage::Tree
+    }
+
+    pub(super) fn research_action_for_command(&self, command: Command) -> Option<Action> {
+        let research = &self.cards.research;
+        let selected_queued = research.selected_queued;
+        let queued = || research.manager.queue().get(selected_queued).copied();
+        match (research.page, command) {
+            (_, Command::ManualResearch) => Some(Action::ManualResearch),
+            (ResearchPage::Tree, Command::ResearchTree) => {
+                Some(Action::ShowResearchPage(ResearchPage::Projects))
+            }
+            (_, Command::ResearchTree) => Some(Action::ShowResearchPage(ResearchPage::Tree)),
+            (ResearchPage::Tree, _) => self.research_tree_action_for_command(command),
+            (ResearchPage::Projects, Command::Pause) => Some(Action::PauseResearch),
+            (ResearchPage::Projects, Command::Right) => {
+                Some(Action::ShowResearchPage(ResearchPage::Queue))
+            }
+            (ResearchPage::Queue, Command::Left) => {
+                Some(Action::ShowResearchPage(ResearchPage::Projects))
+            }
+            (ResearchPage::Projects, Command::Choose(id)) => {
+                let &project = research.manager.available().get_index(id.checked_sub(1)?)?;
+                if research.manager.active().is_none() {
+                    Some(Action::StartResearch(project))
+                } else {
+                    Some(Action::QueueResearch(project))
+                }
+            }
+            (ResearchPage::Queue, Command::Choose(id)) => {
+                Some(Action::SelectQueuedResearch(id.checked_sub(1)?))
+            }
+            (ResearchPage::Queue, Command::MoveEarlier) => Some(Action::MoveQueuedResearch(
+                queued()?,
+                selected_queued.checked_sub(1)?,
+            )),
+            (ResearchPage::Queue, Command::MoveLater) => {
+                Some(Action::MoveQueuedResearch(queued()?, selected_queued + 1))
+            }
+            (ResearchPage::Queue, Command::Remove) => Some(Action::DequeueResearch(queued()?)),
+            (ResearchPage::Queue, Command::Start) => Some(Action::StartResearch(queued()?)),
+            _ => None,
         }
     }
 
+    pub(crate) fn can_show_research_page(&self, page: ResearchPage) -> bool {
+        self.cards.research.discovered && self.cards.research.page != page
+    }
+
+    pub(crate) fn show_research_page(&mut self, page: ResearchPage) {
+        assert!(self.can_show_research_page(page));
+        self.cards.research.page = page;
+    }
+
+    /// Available and queued projects can be started, pausing the active one.
+    pub(crate) fn can_start_research(&self, project: Project) -> bool {
+        let manager = &self.cards.research.manager;
+        self.cards.research.discovered
+            && (manager.available().contains(&project) || manager.queue().contains(&project))
+    }
+
+    pub(crate) fn start_research(&mut self, project: Project) {
+        assert!(self.can_start_research(project));
+        let research = &mut self.cards.research;
+        research.manager.switch_to(project);
+        research.keep_selection_in_queue();
+    }
+
+    pub(crate) fn can_pause_research(&self) -> bool {
+        self.cards.research.manager.active().is_some()
+    }
+
+    pub(crate) fn pause_research(&mut self) {
+        assert!(self.can_pause_research());
+        let research = &mut self.cards.research;
+        research.manager.pause();
+        research.keep_selection_in_queue();
+    }
+
+    pub(crate) fn can_queue_research(&self, project: Project) -> bool {
+        self.cards.research.discovered
+            && self.cards.research.manager.active().is_some()
+            && self.cards.research.manager.available().contains(&project)
+    }
+
+    pub(crate) fn queue_research(&mut self, project: Project) {
+        assert!(self.can_queue_research(project));
+        self.cards.research.manager.enqueue(project);
+    }
+
+    pub(crate) fn can_select_queued_research(&self, index: usize) -> bool {
+        index < self.cards.research.manager.queue().len()
+    }
+
+    pub(crate) fn select_queued_research(&mut self, index: usize) {
+        assert!(self.can_select_queued_research(index));
+        self.cards.research.selected_queued = index;
+    }
+
+    pub(crate) fn can_move_queued_research(&self, project: Project, to: usize) -> bool {
+        let queue = self.cards.research.manager.queue();
+        queue.contains(&project) && to < queue.len()
+    }
+
+    /// The selection follows the moved project.
+    pub(crate) fn move_queued_research(&mut self, project: Project, to: usize) {
+        assert!(self.can_move_queued_research(project, to));
+        self.cards.research.manager.move_queued(project, to);
+        self.cards.research.selected_queued = to;
+    }
+
+    pub(crate) fn can_dequeue_research(&self, project: Project) -> bool {
+        self.cards.research.manager.queue().contains(&project)
+    }
+
+    pub(crate) fn dequeue_research(&mut self, project: Project) {
+        assert!(self.can_dequeue_research(project));
+        let research = &mut self.cards.research;
+        research.manager.dequeue(project);
+        research.keep_selection_in_queue();
+    }
+
+    pub(crate) fn can_research_manually(&self) -> bool {
+        self.cards.research.manager.active().is_some()
+            && self.cards.research.manual_research_per_click != Quantity::default()
+    }
+
+    pub(crate) fn research_manually(&mut self) {
+        assert!(self.can_research_manually());
+        let (_, progress) = self.cards.research.manager.active_mut().unwrap();
+        *progress += self.cards.research.manual_research_per_click;
+        self.finish_research();
+    }
+
     pub(super) fn simulate_card_research(&mut self, delta: Duration) {
         if !self.cards.research.discovered && self.cards.staff.researcher != Quantity::default() {
             self.cards.research.discovered = true;
@@ -330,3 +670,92 @@ impl World {
         self.simulate_research_manager(delta);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        input::Command,
+        world::{action::Action, cards::Card, quantity::Quantity, World},
+    };
+
+    use super::Trigger;
+
+    fn world_with_two_projects() -> World {
+        let mut world = World::new();
+        world.cards.staff.researcher = Quantity::new(1);
+        world
+            .cards
+            .research
+            .manager
+            .trigger(Trigger::ManualResearch);
+        world.simulate(Duration::TICK);
+        world
+    }
+
+    #[test]
+    fn queue_carries_over_surplus() {
+        let mut world = world_with_two_projects();
+        let available = world.cards.research.manager.available().clone();
+        let (&catchier_flyer, &recycling) = (&available[0], &available[1]);
+        assert!(world.apply_action(Action::StartResearch(catchier_flyer)));
+        assert!(world.apply_action(Action::QueueResearch(recycling)));
+
+        // One researcher needs 30s for the first project, the other 30s go to the queued one.
+        world.simulate(Duration::TICK + Duration::MINUTE);
+        let &(active, progress) = world.cards.research.manager.active().unwrap();
+        assert_eq!(active, recycling);
+        assert_eq!(progress, Quantity::checked_fraction(1, 2).unwrap());
+        assert!(world
+            .cards
+            .research
+            .manager
+            .finished()
+            .contains(&catchier_flyer));
+        assert!(world.cards.research.manager.queue().is_empty());
+    }
+
+    #[test]
+    fn switching_keeps_progress() {
+        let mut world = world_with_two_projects();
+        let available = world.cards.research.manager.available().clone();
+        let (&catchier_flyer, &recycling) = (&available[0], &available[1]);
+
+        assert!(world.apply_action(Action::StartResearch(recycling)));
+        world.simulate(world.get_total_ticks() + Duration::from_seconds(30));
+        assert!(world.apply_action(Action::StartResearch(catchier_flyer)));
+        assert_eq!(
+            world.cards.research.manager.progress(recycling),
+            Quantity::checked_fraction(1, 2).unwrap()
+        );
+
+        assert!(world.apply_action(Action::PauseResearch));
+        assert!(world.cards.research.manager.active().is_none());
+        assert!(world.apply_action(Action::StartResearch(recycling)));
+        // Only the remaining minute is missing.
+        world.simulate(world.get_total_ticks() + Duration::MINUTE);
+        assert!(world.cards.research.manager.finished().contains(&recycling));
+    }
+
+    #[test]
+    fn tree_keeps_arrow_keys() {
+        let mut world = world_with_two_projects();
+        world.cards.selected = Card::Research;
+        world.handle_command(Command::ResearchTree);
+
+        let layers = world.cards.research.manager.tree().layers();
+        assert_eq!(world.highlighted_research(), Some(layers[0][0]));
+        world.handle_command(Command::Up);
+        assert_eq!(world.cards.selected, Card::Research);
+        world.handle_command(Command::Right);
+        assert_eq!(world.highlighted_research(), Some(layers[1][0]));
+        for _ in 0..layers[1].len() {
+            world.handle_command(Command::Down);
+        }
+        assert_eq!(world.highlighted_research(), layers[1].last().copied());
+
+        world.handle_command(Command::ResearchTree);
+        world.handle_command(Command::Up);
+        assert_eq!(world.cards.selected, Card::CO2);
+    }
+}
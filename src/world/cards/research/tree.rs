@@ -0,0 +1,488 @@
+use std::{error::Error, fmt::Display, fs, io, path::Path};
+
+use indexmap::{IndexMap, IndexSet};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    duration::Duration,
+    world::{
+        quantity::{
+            format::Notation,
+            types::{Emission, Flyer, Person, ResearchPoints},
+            Quantity, QuantityType,
+        },
+        rate::Rate,
+    },
+};
+
+const BUILTIN_RESEARCH_TREE: &str = include_str!("../../../../resources/research.ron");
+const SECONDS_PER_DAY: u128 = 24 * 60 * 60;
+
+/// Index of a project in its [`ResearchTree`].
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Project(usize);
+
+/// Something that happens in the game and makes projects available.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Trigger {
+    ManualResearch,
+}
+
+impl Trigger {
+    /// Every trigger the game fires somewhere, projects behind other triggers stay locked.
+    const FIRED_BY_THE_GAME: [Trigger; 1] = [Trigger::ManualResearch];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "manual_research" => Some(Self::ManualResearch),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Effect {
+    FlyerEffectiveness(Rate<Emission>),
+    FlyerPersuasiveness(Quantity<Person>),
+    /// Flyers that fit into storage, not counting bought storage rooms.
+    MaximalFlyer(Quantity<Flyer>),
+    SupporterLifetime(Duration),
+    Recycling,
+}
+
+impl Effect {
+    pub fn describe(&self, notation: Notation) -> String {
+        match self {
+            Effect::FlyerEffectiveness(effectiveness) => format!(
+                "Supporters: {}/y",
+                effectiveness.per(Duration::YEAR).stringify(0, notation)
+            ),
+            Effect::FlyerPersuasiveness(persuasiveness) => {
+                format!("Flyer convinces {}", persuasiveness.stringify(2, notation))
+            }
+            Effect::MaximalFlyer(maximal_flyer) => {
+                format!("Stores {} flyers", maximal_flyer.stringify(0, notation))
+            }
+            Effect::SupporterLifetime(lifetime) => format!(
+                "Supporters stay {}d",
+                lifetime.ticks() / Duration::from_seconds(SECONDS_PER_DAY).ticks()
+            ),
+            Effect::Recycling => "Recycles flyers".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDefinition {
+    pub id: String,
+    pub name: String,
+    pub cost: Quantity<ResearchPoints>,
+    pub requires: Vec<Project>,
+    pub unlocked_by: Option<Trigger>,
+    pub effects: Vec<Effect>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResearchTree {
+    projects: Vec<ProjectDefinition>,
+}
+
+#[derive(Deserialize)]
+struct RawProject {
+    id: String,
+    name: String,
+    cost: (u128, u128),
+    #[serde(default)]
+    requires: Vec<String>,
+    #[serde(default)]
+    unlocked_by: Option<String>,
+    #[serde(default)]
+    effects: Vec<RawEffect>,
+}
+
+#[derive(Deserialize)]
+enum RawEffect {
+    Set { target: String, value: (u128, u128) },
+    Enable { target: String },
+}
+
+#[derive(Debug)]
+pub enum ResearchTreeError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    DuplicateId(String),
+    UnknownPrerequisite {
+        project: String,
+        prerequisite: String,
+    },
+    UnknownTrigger {
+        project: String,
+        trigger: String,
+    },
+    UnknownEffectTarget {
+        project: String,
+        target: String,
+    },
+    InvalidValue {
+        project: String,
+        target: String,
+    },
+    Cycle(Vec<String>),
+    Unreachable(Vec<String>),
+}
+
+impl Display for ResearchTreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "cannot read research tree: {error}"),
+            Self::Parse(error) => write!(f, "invalid research tree: {error}"),
+            Self::DuplicateId(id) => write!(f, "research project {id} is defined twice"),
+            Self::UnknownPrerequisite {
+                project,
+                prerequisite,
+            } => write!(f, "{project} requires unknown project {prerequisite}"),
+            Self::UnknownTrigger { project, trigger } => {
+                write!(f, "{project} is unlocked by unknown trigger {trigger}")
+            }
+            Self::UnknownEffectTarget { project, target } => {
+                write!(f, "{project} has an effect on unknown target {target}")
+            }
+            Self::InvalidValue { project, target } => write!(
+                f,
+                "{project} sets {target} to a value that cannot be represented exactly"
+            ),
+            Self::Cycle(ids) => write!(
+                f,
+                "research projects require each other: {}",
+                ids.join(" -> ")
+            ),
+            Self::Unreachable(ids) => write!(
+                f,
+                "research projects can never be unlocked: {}",
+                ids.join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for ResearchTreeError {}
+
+fn fraction<Q: QuantityType>(
+    (n, d): (u128, u128),
+    project: &str,
+    target: &str,
+) -> Result<Quantity<Q>, ResearchTreeError> {
+    Quantity::checked_fraction(n, d).ok_or_else(|| ResearchTreeError::InvalidValue {
+        project: project.to_owned(),
+        target: target.to_owned(),
+    })
+}
+
+fn parse_effect(effect: RawEffect, project: &str) -> Result<Effect, ResearchTreeError> {
+    let unknown = |target: &str| ResearchTreeError::UnknownEffectTarget {
+        project: project.to_owned(),
+        target: target.to_owned(),
+    };
+
+    match effect {
+        RawEffect::Set { target, value } => match target.as_str() {
+            "flyer_effectiveness" => {
+                let per_year = fraction(value, project, &target)?;
+                Rate::checked_new(per_year, Duration::YEAR)
+                    .map(Effect::FlyerEffectiveness)
+                    .ok_or_else(|| ResearchTreeError::InvalidValue {
+                        project: project.to_owned(),
+                        target: target.clone(),
+                    })
+            }
+            "flyer_persuasiveness" => Ok(Effect::FlyerPersuasiveness(fraction(
+                value, project, &target,
+            )?)),
+            "maximal_flyer" => Ok(Effect::MaximalFlyer(fraction(value, project, &target)?)),
+            "supporter_lifetime" => {
+                let (days, denominator) = value;
+                days.checked_mul(SECONDS_PER_DAY)
+                    .filter(|&seconds| denominator != 0 && seconds.is_multiple_of(denominator))
+                    .and_then(|seconds| Duration::SECOND.checked_mul(seconds / denominator))
+                    .map(Effect::SupporterLifetime)
+                    .ok_or_else(|| ResearchTreeError::InvalidValue {
+                        project: project.to_owned(),
+                        target: target.clone(),
+                    })
+            }
+            _ => Err(unknown(&target)),
+        },
+        RawEffect::Enable { target } => match target.as_str() {
+            "recycling" => Ok(Effect::Recycling),
+            _ => Err(unknown(&target)),
+        },
+    }
+}
+
+impl ResearchTree {
+    pub fn builtin() -> Self {
+        Self::from_ron(BUILTIN_RESEARCH_TREE).expect("builtin research tree is valid")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ResearchTreeError> {
+        Self::from_ron(&fs::read_to_string(path).map_err(ResearchTreeError::Io)?)
+    }
+
+    pub fn from_ron(definitions: &str) -> Result<Self, ResearchTreeError> {
+        let raw_projects: Vec<RawProject> =
+            ron::from_str(definitions).map_err(ResearchTreeError::Parse)?;
+
+        let mut ids = IndexMap::new();
+        for (index, raw_project) in raw_projects.iter().enumerate() {
+            if ids.insert(raw_project.id.clone(), Project(index)).is_some() {
+                return Err(ResearchTreeError::DuplicateId(raw_project.id.clone()));
+            }
+        }
+
+        let mut projects = Vec::new();
+        for raw_project in raw_projects {
+            let id = raw_project.id;
+
+            let requires = raw_project
+                .requires
+                .into_iter()
+                .map(|prerequisite| {
+                    ids.get(&prerequisite).copied().ok_or_else(|| {
+                        ResearchTreeError::UnknownPrerequisite {
+                            project: id.clone(),
+                            prerequisite,
+                        }
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+
+            let unlocked_by = raw_project
+                .unlocked_by
+                .map(|trigger| {
+                    Trigger::from_name(&trigger).ok_or_else(|| ResearchTreeError::UnknownTrigger {
+                        project: id.clone(),
+                        trigger,
+                    })
+                })
+                .transpose()?;
+
+            let effects = raw_project
+                .effects
+                .into_iter()
+                .map(|effect| parse_effect(effect, &id))
+                .collect::<Result<_, _>>()?;
+
+            projects.push(ProjectDefinition {
+                cost: fraction(raw_project.cost, &id, "cost")?,
+                id,
+                name: raw_project.name,
+                requires,
+                unlocked_by,
+                effects,
+            });
+        }
+
+        let tree = Self { projects };
+        tree.check_for_cycles()?;
+        tree.check_reachability(&Trigger::FIRED_BY_THE_GAME)?;
+        Ok(tree)
+    }
+
+    fn check_for_cycles(&self) -> Result<(), ResearchTreeError> {
+        fn visit(
+            tree: &ResearchTree,
+            project: Project,
+            path: &mut Vec<Project>,
+            done: &mut IndexSet<Project>,
+        ) -> Result<(), ResearchTreeError> {
+            if done.contains(&project) {
+                return Ok(());
+            }
+            if let Some(start) = path.iter().position(|&p| p == project) {
+                let mut cycle: Vec<_> = path[start..]
+                    .iter()
+                    .map(|&p| tree.definition(p).id.clone())
+                    .collect();
+                cycle.push(tree.definition(project).id.clone());
+                return Err(ResearchTreeError::Cycle(cycle));
+            }
+
+            path.push(project);
+            for &prerequisite in &tree.definition(project).requires {
+                visit(tree, prerequisite, path, done)?;
+            }
+            path.pop();
+            done.insert(project);
+            Ok(())
+        }
+
+        let mut done = IndexSet::new();
+        for project in self.projects() {
+            visit(self, project, &mut Vec::new(), &mut done)?;
+        }
+        Ok(())
+    }
+
+    /// Starting from the projects that need no other project, unlocks everything possible with
+    /// the `fired` triggers.
+    fn check_reachability(&self, fired: &[Trigger]) -> Result<(), ResearchTreeError> {
+        let mut reachable = IndexSet::new();
+        loop {
+            let newly_reachable: Vec<_> = self
+                .projects()
+                .filter(|project| !reachable.contains(project))
+                .filter(|&project| {
+                    let definition = self.definition(project);
+                    definition
+                        .unlocked_by
+                        .is_none_or(|trigger| fired.contains(&trigger))
+                        && definition
+                            .requires
+                            .iter()
+                            .all(|prerequisite| reachable.contains(prerequisite))
+                })
+                .collect();
+            if newly_reachable.is_empty() {
+                break;
+            }
+            reachable.extend(newly_reachable);
+        }
+
+        let unreachable: Vec<_> = self
+            .projects()
+            .filter(|project| !reachable.contains(project))
+            .map(|project| self.definition(project).id.clone())
+            .collect();
+        if unreachable.is_empty() {
+            Ok(())
+        } else {
+            Err(ResearchTreeError::Unreachable(unreachable))
+        }
+    }
+
+    pub fn projects(&self) -> impl Iterator<Item = Project> {
+        (0..self.projects.len()).map(Project)
+    }
+
+    pub fn definition(&self, project: Project) -> &ProjectDefinition {
+        &self.projects[project.0]
+    }
+
+    /// Groups the projects by the length of their longest chain of prerequisites,
+    /// so every project comes after everything it requires.
+    pub fn layers(&self) -> Vec<Vec<Project>> {
+        fn depth(tree: &ResearchTree, project: Project, depths: &mut [Option<usize>]) -> usize {
+            if let Some(depth) = depths[project.0] {
+                return depth;
+            }
+            let result = tree
+                .definition(project)
+                .requires
+                .iter()
+                .map(|&prerequisite| depth(tree, prerequisite, depths) + 1)
+                .max()
+                .unwrap_or(0);
+            depths[project.0] = Some(result);
+            result
+        }
+
+        let mut depths = vec![None; self.projects.len()];
+        let mut layers: Vec<Vec<Project>> = Vec::new();
+        for project in self.projects() {
+            let depth = depth(self, project, &mut depths);
+            if layers.len() <= depth {
+                layers.resize_with(depth + 1, Vec::new);
+            }
+            layers[depth].push(project);
+        }
+        layers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResearchTree, ResearchTreeError};
+
+    #[test]
+    fn builtin_is_valid() {
+        ResearchTree::builtin();
+    }
+
+    #[test]
+    fn rejects_unknown_effect_targets() {
+        let error = ResearchTree::from_ron(
+            r#"[(id: "a", name: "A", cost: (1, 1), effects: [Enable(target: "teleportation")])]"#,
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ResearchTreeError::UnknownEffectTarget { .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_unrepresentable_lifetimes() {
+        let error = ResearchTree::from_ron(&format!(
+            r#"[(id: "a", name: "A", cost: (1, 1), effects: [Set(target: "supporter_lifetime", value: ({}, 1))])]"#,
+            u128::MAX / 2
+        ))
+        .unwrap_err();
+
+        assert!(matches!(error, ResearchTreeError::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let error = ResearchTree::from_ron(
+            r#"[
+                (id: "a", name: "A", cost: (1, 1)),
+                (id: "b", name: "B", cost: (1, 1), requires: ["a", "c"]),
+                (id: "c", name: "C", cost: (1, 1), requires: ["b"]),
+            ]"#,
+        )
+        .unwrap_err();
+
+        assert!(matches!(error, ResearchTreeError::Cycle(ids) if ids == ["b", "c", "b"]));
+    }
+
+    #[test]
+    fn rejects_projects_behind_triggers_that_never_fire() {
+        let tree = ResearchTree::from_ron(
+            r#"[
+                (id: "a", name: "A", cost: (1, 1)),
+                (id: "b", name: "B", cost: (1, 1), unlocked_by: Some("manual_research")),
+                (id: "c", name: "C", cost: (1, 1), requires: ["a", "b"]),
+            ]"#,
+        )
+        .unwrap();
+
+        let error = tree.check_reachability(&[]).unwrap_err();
+        assert!(matches!(error, ResearchTreeError::Unreachable(ids) if ids == ["b", "c"]));
+    }
+
+    #[test]
+    fn layers_follow_prerequisites() {
+        let tree = ResearchTree::from_ron(
+            r#"[
+                (id: "c", name: "C", cost: (1, 1), requires: ["b", "a"]),
+                (id: "a", name: "A", cost: (1, 1)),
+                (id: "b", name: "B", cost: (1, 1), requires: ["a"]),
+                (id: "d", name: "D", cost: (1, 1)),
+            ]"#,
+        )
+        .unwrap();
+
+        let ids: Vec<Vec<_>> = tree
+            .layers()
+            .iter()
+            .map(|layer| {
+                layer
+                    .iter()
+                    .map(|&project| tree.definition(project).id.as_str())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(ids, [vec!["a", "d"], vec!["b"], vec!["c"]]);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{
+    duration::{stringify_estimate, Duration, Style},
+    grid::{text::Text, Cell, Color, MutGridView},
+    input::Command,
+    world::{action::Action, World},
+};
+
+use super::{Project, ProjectStatus};
+
+/// Lines at the bottom of the page that describe the highlighted project.
+const LINES_DETAILS: usize = 4;
+/// Horizontal distance between two layers of the tree.
+const LAYER_WIDTH: usize = 3;
+
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+fn line_character(connections: u8) -> char {
+    match connections {
+        0 => ' ',
+        c if c == LEFT | RIGHT || c == LEFT || c == RIGHT => '─',
+        c if c == UP | DOWN || c == UP || c == DOWN => '│',
+        c if c == LEFT | DOWN => '┐',
+        c if c == LEFT | UP => '┘',
+        c if c == RIGHT | DOWN => '┌',
+        c if c == RIGHT | UP => '└',
+        c if c == LEFT | RIGHT | DOWN => '┬',
+        c if c == LEFT | RIGHT | UP => '┴',
+        c if c == UP | DOWN | RIGHT => '├',
+        c if c == UP | DOWN | LEFT => '┤',
+        _ => '┼',
+    }
+}
+
+impl ProjectStatus {
+    fn glyph(self) -> char {
+        match self {
+            ProjectStatus::Finished => '√',
+            ProjectStatus::Active => '►',
+            ProjectStatus::Available => 'o',
+            ProjectStatus::Locked => '·',
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            ProjectStatus::Finished => Color::GREEN,
+            ProjectStatus::Active => Color::YELLOW,
+            ProjectStatus::Available => Color::WHITE,
+            ProjectStatus::Locked => Color::BLUE,
+        }
+    }
+}
+
+/// Where a project sits in the tree, as (layer, index in layer).
+fn position(layers: &[Vec<Project>], project: Project) -> (usize, usize) {
+    layers
+        .iter()
+        .enumerate()
+        .find_map(|(layer_id, layer)| {
+            layer
+                .iter()
+                .position(|&p| p == project)
+                .map(|row| (layer_id, row))
+        })
+        .unwrap()
+}
+
+impl World {
+    pub(super) fn highlighted_research(&self) -> Option<Project> {
+        let research = &self.cards.research;
+        research
+            .highlighted
+            .or_else(|| research.manager.tree().layers().first()?.first().copied())
+    }
+
+    pub(super) fn research_tree_action_for_command(&self, command: Command) -> Option<Action> {
+        let layers = self.cards.research.manager.tree().layers();
+        let (layer, row) = position(&layers, self.highlighted_research()?);
+        let (layer, row) = match command {
+            Command::Up => (layer, row.checked_sub(1)?),
+            Command::Down => (layer, row + 1),
+            Command::Left => (layer.checked_sub(1)?, row),
+            Command::Right => (layer + 1, row),
+            _ => return None,
+        };
+        let layer = layers.get(layer)?;
+        let &project = layer.get(row.min(layer.len() - 1))?;
+        Some(Action::HighlightResearch(project))
+    }
+
+    pub(crate) fn can_highlight_research(&self, project: Project) -> bool {
+        self.cards.research.discovered && self.highlighted_research() != Some(project)
+    }
+
+    pub(crate) fn highlight_research(&mut self, project: Project) {
+        assert!(self.can_highlight_research(project));
+        self.cards.research.highlighted = Some(project);
+    }
+
+    pub(super) fn render_research_tree(&self, mut view: MutGridView<'_, Cell>) {
+        let manager = &self.cards.research.manager;
+        let layers = manager.tree().layers();
+        let Some(highlighted) = self.highlighted_research() else {
+            view.print_overflowing(1, "There is nothing to research.".to_owned().into());
+            return;
+        };
+
+        // Lines are collected for the whole tree first, so crossing arrows join up.
+        let height = layers.iter().map(Vec::len).max().unwrap();
+        let width = (layers.len() - 1) * LAYER_WIDTH + 1;
+        let mut connections = vec![vec![0; width]; height];
+        for (layer_id, layer) in layers.iter().enumerate() {
+            let column = layer_id * LAYER_WIDTH;
+            for (row, &project) in layer.iter().enumerate() {
+                for &prerequisite in &manager.definition(project).requires {
+                    let (from_layer, from_row) = position(&layers, prerequisite);
+                    let turn = column - 1;
+                    for connection in &mut connections[from_row][from_layer * LAYER_WIDTH + 1..turn]
+                    {
+                        *connection |= LEFT | RIGHT;
+                    }
+                    if from_row == row {
+                        connections[row][turn] |= LEFT | RIGHT;
+                        continue;
+                    }
+                    let (top, bottom) = (from_row.min(row), from_row.max(row));
+                    connections[from_row][turn] |= LEFT | if from_row == top { DOWN } else { UP };
+                    connections[row][turn] |= RIGHT | if row == top { DOWN } else { UP };
+                    for line in connections.iter_mut().take(bottom).skip(top + 1) {
+                        line[turn] |= UP | DOWN;
+                    }
+                }
+            }
+        }
+
+        // Scrolls so the highlighted project stays visible.
+        let tree_height = view.height() - LINES_DETAILS;
+        let (highlighted_layer, highlighted_row) = position(&layers, highlighted);
+        let first_row = (highlighted_row + 1).saturating_sub(tree_height);
+        let first_column = (highlighted_layer * LAYER_WIDTH + 1).saturating_sub(view.width());
+
+        let mut text_at = |row: usize, column: usize, text: Text| {
+            if let (Some(line), Some(char_id)) =
+                (row.checked_sub(first_row), column.checked_sub(first_column))
+            {
+                if line < tree_height && char_id < view.width() {
+                    view.print(line, char_id, text);
+                }
+            }
+        };
+        for (row, line) in connections.iter().enumerate() {
+            for (column, &connection) in line.iter().enumerate() {
+                if connection != 0 {
+                    text_at(row, column, line_character(connection).to_string().into());
+                }
+            }
+        }
+        for (layer_id, layer) in layers.iter().enumerate() {
+            for (row, &project) in layer.iter().enumerate() {
+                let status = manager.status(project);
+                let (foreground, background) = if project == highlighted {
+                    (Color::BLACK, Some(status.color()))
+                } else {
+                    (status.color(), None)
+                };
+                text_at(
+                    row,
+                    layer_id * LAYER_WIDTH,
+                    Text::new().styled(&status.glyph().to_string(), Some(foreground), background),
+                );
+            }
+        }
+
+        let definition = manager.definition(highlighted);
+        let remaining = if manager.status(highlighted) == ProjectStatus::Finished {
+            "done".to_owned()
+        } else {
+            stringify_estimate(
+                Duration::from_quantity_and_rate_approximation(
+                    definition.cost - manager.progress(highlighted),
+                    self.research_rate(),
+                ),
+                Style::Compact,
+            )
+        };
+        let mut details = vec![
+            definition.name.clone(),
+            format!(
+                "Cost: {} [{}]",
+                definition.cost.stringify(2, self.notation),
+                remaining
+            ),
+        ];
+        details.extend(
+            definition
+                .effects
+                .iter()
+                .map(|effect| effect.describe(self.notation)),
+        );
+        for (line, detail) in details.into_iter().take(LINES_DETAILS).enumerate() {
+            let detail: String = detail.chars().take(view.width()).collect();
+            view.print(tree_height + line, 0, detail.into());
+        }
+    }
+}
@@ -1,15 +1,43 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
     duration::Duration,
     grid::{Cell, Color, MutGridView},
-    input::Input,
+    input::Command,
     world::{
-        quantity::{types::Person, Quantity},
+        action::Action,
+        message::{Message, STANDARD_MESSAGE_DURATION},
+        quantity::{
+            types::{Flyer, Person},
+            Quantity,
+        },
+        rate::{Rate, RatePerUnit},
         World,
     },
 };
 
 use super::abstract_card::AbstractCard;
 
+/// One in this many supporters is willing to join the staff.
+const SUPPORTERS_PER_RECRUIT: u128 = 10;
+pub const HANDOUT_RATE_PER_ACTIVIST: RatePerUnit<Flyer, Person> =
+    RatePerUnit::new(Rate::new(Quantity::new(1), Duration::from_seconds(10)));
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Role {
+    Research,
+    Activism,
+}
+
+impl Role {
+    fn other(self) -> Self {
+        match self {
+            Role::Research => Role::Activism,
+            Role::Activism => Role::Research,
+        }
+    }
+}
+
 impl AbstractCard for Staff {
     fn menu_string(&self) -> String {
         "Staff".into()
@@ -20,26 +48,226 @@ impl AbstractCard for Staff {
     }
 
     fn is_visible(&self) -> bool {
-        false
+        self.discovered
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Staff {
+    #[serde(default)]
+    discovered: bool,
     pub researcher: Quantity<Person>,
-    _activists: Quantity<Person>,
+    #[serde(alias = "_activists")]
+    pub activists: Quantity<Person>,
+    /// Supporters that joined the staff, in contrast to researchers that came on their own.
+    #[serde(default)]
+    recruited: Quantity<Person>,
+    /// Flyers the activists are about to hand out, only the fractional part is kept while idle.
+    #[serde(default)]
+    handout_progress: Quantity<Flyer>,
 }
 impl Staff {
     pub fn new() -> Staff {
         Staff {
+            discovered: false,
             researcher: Quantity::default(),
-            _activists: Quantity::default(),
+            activists: Quantity::default(),
+            recruited: Quantity::default(),
+            handout_progress: Quantity::default(),
+        }
+    }
+
+    fn members_mut(&mut self, role: Role) -> &mut Quantity<Person> {
+        match role {
+            Role::Research => &mut self.researcher,
+            Role::Activism => &mut self.activists,
+        }
+    }
+
+    fn members(&self, role: Role) -> Quantity<Person> {
+        match role {
+            Role::Research => self.researcher,
+            Role::Activism => self.activists,
         }
     }
 }
 
 impl World {
-    pub(super) fn render_card_staff(&mut self, _input: &Input, mut _view: MutGridView<'_, Cell>) {}
+    fn recruitable_supporters(&self) -> u128 {
+        let willing = self
+            .cards
+            .activism
+            .population
+            .supporting_people()
+            .whole_amount()
+            / SUPPORTERS_PER_RECRUIT;
+        willing.saturating_sub(self.cards.staff.recruited.whole_amount())
+    }
+
+    pub(super) fn render_card_staff(&self, mut view: MutGridView<'_, Cell>) {
+        let staff = &self.cards.staff;
+
+        view.print(
+            0,
+            0,
+            format!(
+                "Researchers: {}",
+                staff.researcher.stringify(0, self.notation)
+            )
+            .into(),
+        );
+        view.print(
+            1,
+            0,
+            format!(
+                " {} research",
+                self.research_rate().stringify(4, self.notation)
+            )
+            .into(),
+        );
+        view.print(
+            2,
+            0,
+            format!("Activists: {}", staff.activists.stringify(0, self.notation)).into(),
+        );
+        view.print(
+            3,
+            0,
+            format!(
+                " {} flyers",
+                (HANDOUT_RATE_PER_ACTIVIST * staff.activists).stringify(2, self.notation)
+            )
+            .into(),
+        );
+
+        if staff.activists != Quantity::default() && !self.is_staff_paid() {
+            view.print(4, 0, " (waiting for salaries)".to_owned().into());
+        } else if staff.activists != Quantity::default()
+            && self.cards.activism.flyer == Quantity::default()
+        {
+            view.print(4, 0, " (waiting for flyers)".to_owned().into());
+        }
+
+        view.print(
+            5,
+            0,
+            format!("Willing to join: {}", self.recruitable_supporters()).into(),
+        );
+        view.print(6, 0, "1: recruit researcher".to_owned().into());
+        view.print(7, 0, "2: recruit activist".to_owned().into());
+        view.print(8, 0, "←: move to research".to_owned().into());
+        view.print(9, 0, "→: move to activism".to_owned().into());
+    }
+
+    pub(super) fn staff_action_for_command(&self, command: Command) -> Option<Action> {
+        match command {
+            Command::Choose(1) => Some(Action::Recruit(Role::Research)),
+            Command::Choose(2) => Some(Action::Recruit(Role::Activism)),
+            Command::Left => Some(Action::Reassign(Role::Research)),
+            Command::Right => Some(Action::Reassign(Role::Activism)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn can_recruit(&self) -> bool {
+        self.cards.staff.discovered && self.recruitable_supporters() != 0
+    }
 
-    pub(super) fn simulate_card_staff(&mut self, _delta: Duration) {}
+    pub(crate) fn recruit(&mut self, role: Role) {
+        assert!(self.can_recruit());
+        let staff = &mut self.cards.staff;
+        staff.recruited += 1;
+        *staff.members_mut(role) += 1;
+    }
+
+    /// Moves one staff member from the other role to `role`.
+    pub(crate) fn can_reassign(&self, role: Role) -> bool {
+        self.cards.staff.discovered && self.cards.staff.members(role.other()) != Quantity::default()
+    }
+
+    pub(crate) fn reassign(&mut self, role: Role) {
+        assert!(self.can_reassign(role));
+        let staff = &mut self.cards.staff;
+        *staff.members_mut(role.other()) -= Quantity::new(1);
+        *staff.members_mut(role) += 1;
+    }
+
+    pub(super) fn simulate_card_staff(&mut self, delta: Duration) {
+        if !self.cards.staff.discovered && self.recruitable_supporters() != 0 {
+            self.cards.staff.discovered = true;
+            self.messages.queue(Message::new(
+                "Supporters want to join your staff.".into(),
+                STANDARD_MESSAGE_DURATION,
+            ))
+        }
+
+        self.simulate_activists(delta);
+    }
+
+    /// All flyers of the time step are handed out as one batch, however long it is.
+    fn simulate_activists(&mut self, delta: Duration) {
+        let delta = self.paid_time(delta);
+        if self.is_in_prolog() || delta == Duration::INSTANT {
+            return;
+        }
+
+        let staff = &mut self.cards.staff;
+        staff.handout_progress += (HANDOUT_RATE_PER_ACTIVIST * staff.activists) * delta;
+        let planned = staff.handout_progress.whole_amount();
+        let handed_out = self.handout_flyers(planned);
+
+        // Activists without flyers wait instead of building up a backlog.
+        let progress = &mut self.cards.staff.handout_progress;
+        *progress -= Quantity::new(handed_out);
+        let idle = progress.whole_amount();
+        *progress -= Quantity::new(idle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        world::{action::Action, quantity::Quantity, World},
+    };
+
+    use super::Role;
+
+    #[test]
+    fn activists_hand_out_flyers() {
+        let mut world = World::new_after_prolog();
+        world.cards.activism.flyer = Quantity::new(100);
+        world.cards.staff.activists = Quantity::new(1);
+
+        // 1.5 flyers, the half is kept for later.
+        world.simulate(Duration::from_seconds(15));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(99));
+        world.simulate(Duration::from_seconds(20));
+        assert_eq!(world.cards.activism.flyer, Quantity::new(98));
+
+        // A long absence only uses up the flyers there are.
+        world.cards.staff.activists = Quantity::new(3);
+        world.simulate(Duration::from_seconds(24 * 60 * 60));
+        assert_eq!(world.cards.activism.flyer, Quantity::default());
+        assert!(world.cards.staff.handout_progress < Quantity::new(1));
+        // Ten supporters were won, some of them lapsed again during the day.
+        assert!(world.cards.activism.population.supporting_people() >= Quantity::new(9));
+    }
+
+    #[test]
+    fn recruiting_is_limited_by_supporters() {
+        let mut world = World::new();
+        assert!(!world.apply_action(Action::Recruit(Role::Activism)));
+
+        world.cards.activism.population.regions[0].convince(Quantity::new(25));
+        world.simulate(Duration::SECOND);
+        assert!(world.apply_action(Action::Recruit(Role::Activism)));
+        assert!(world.apply_action(Action::Recruit(Role::Activism)));
+        assert!(!world.apply_action(Action::Recruit(Role::Activism)));
+
+        let researchers = world.cards.staff.researcher;
+        assert!(world.apply_action(Action::Reassign(Role::Research)));
+        assert_eq!(world.cards.staff.researcher, researchers + Quantity::new(1));
+        assert_eq!(world.cards.staff.activists, Quantity::new(1));
+    }
 }
@@ -1,3 +1,4 @@
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Messages {
     entries: VecDeque<Message>,
     current_duration: Duration,
@@ -31,10 +32,13 @@ impl Messages {
 
 use std::collections::VecDeque;
 
+use serde::{Deserialize, Serialize};
+
 use crate::duration::Duration;
 
 pub const STANDARD_MESSAGE_DURATION: Duration = Duration::from_seconds(5);
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Message {
     text: String,
     duration: Duration,
@@ -0,0 +1,104 @@
+use crate::duration::{Duration, Style};
+
+use super::{
+    message::{Message, STANDARD_MESSAGE_DURATION},
+    quantity::{
+        types::{Emission, Person},
+        Quantity,
+    },
+    World,
+};
+
+/// How much time spent with the game closed is simulated at most when resuming a save.
+pub const DEFAULT_MAXIMAL_OFFLINE_DURATION: Duration = Duration::from_seconds(24 * 60 * 60);
+
+pub struct OfflineSummary {
+    pub simulated: Duration,
+    pub saved_emission: Quantity<Emission>,
+    /// Names of the finished projects.
+    pub finished_research: Vec<String>,
+    pub unlocked_researchers: Quantity<Person>,
+}
+
+impl World {
+    /// Simulates `elapsed`, capped at `maximal_duration`, in one step and reports what changed.
+    pub fn catch_up(&mut self, elapsed: Duration, maximal_duration: Duration) -> OfflineSummary {
+        let simulated = if maximal_duration < elapsed {
+            maximal_duration
+        } else {
+            elapsed
+        };
+
+        let saved_emission_before = self.cards.saved_emission();
+        let finished_research_before = self.cards.finished_research().len();
+        let researchers_before = self.cards.researchers();
+
+        self.simulate(self.total_ticks + simulated);
+
+        let summary = OfflineSummary {
+            simulated,
+            saved_emission: self.cards.saved_emission() - saved_emission_before,
+            finished_research: self
+                .cards
+                .finished_research()
+                .iter()
+                .skip(finished_research_before)
+                .map(|&project| self.cards.research_definition(project).name.clone())
+                .collect(),
+            unlocked_researchers: self.cards.researchers() - researchers_before,
+        };
+
+        self.queue_offline_summary(&summary);
+        summary
+    }
+
+    fn queue_offline_summary(&mut self, summary: &OfflineSummary) {
+        if summary.simulated == Duration::INSTANT {
+            return;
+        }
+
+        let mut lines = vec![
+            format!(
+                "While you were away for {}:",
+                summary.simulated.stringify(Style::Verbose)
+            ),
+            format!(
+                "Saved {} CO2e.",
+                summary.saved_emission.stringify(2, self.notation)
+            ),
+        ];
+        lines.extend(
+            summary
+                .finished_research
+                .iter()
+                .map(|name| format!("Finished research: {name}")),
+        );
+        if summary.unlocked_researchers != Quantity::default() {
+            lines.push(format!(
+                "New researchers: {}",
+                summary.unlocked_researchers.stringify(0, self.notation)
+            ));
+        }
+
+        for line in lines {
+            self.messages
+                .queue(Message::new(line, STANDARD_MESSAGE_DURATION));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{duration::Duration, world::World};
+
+    #[test]
+    fn catch_up_is_capped() {
+        let mut world = World::new();
+        world.simulate(Duration::SECOND);
+
+        let summary = world.catch_up(Duration::YEAR, Duration::MINUTE);
+
+        assert_eq!(summary.simulated, Duration::MINUTE);
+        assert_eq!(world.get_total_ticks(), Duration::SECOND + Duration::MINUTE);
+    }
+}
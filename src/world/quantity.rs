@@ -4,36 +4,59 @@ use std::{
     ops::{Add, AddAssign, Mul, Sub, SubAssign},
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::duration::GRANULARITY;
 
+use self::amount::Amount;
+
+mod amount;
 pub mod balance;
+pub mod format;
+
+const OVERFLOW: &str = "quantity overflow";
 
 impl<Q: QuantityType> Quantity<Q> {
-    fn increase_residual(&mut self, increase: u128) {
+    /// The largest quantity there is.
+    pub const MAX: Self = Self {
+        amount: amount::MAX,
+        residual: GRANULARITY - 1,
+        _phantom: PhantomData,
+    };
+
+    /// `increase` has to be below `GRANULARITY * GRANULARITY`.
+    #[must_use]
+    fn checked_increase_residual(mut self, increase: u128) -> Option<Self> {
         self.residual += increase;
         let inc = self.residual / GRANULARITY;
         self.residual %= GRANULARITY;
 
-        // assert!(inc == 0 || inc == 1);
-        self.amount += inc;
+        self.amount = amount::checked_add(self.amount, amount::from_u128(inc))?;
+        Some(self)
     }
 
     pub const fn fraction(n: u128, d: u128) -> Self {
         assert!(d != 0);
-        assert!(GRANULARITY % d == 0);
+        assert!(GRANULARITY.is_multiple_of(d));
         let full = n / d;
         let rest = n % d;
 
         let residual = (GRANULARITY / d) * rest;
 
         Self {
-            amount: full,
+            amount: amount::from_u128(full),
             residual,
             _phantom: PhantomData,
         }
     }
 
-    pub(crate) fn saturating_sub(&mut self, amount: Self) -> Self {
+    /// Like [`Self::fraction`], but `None` instead of a panic for unsupported denominators.
+    pub fn checked_fraction(n: u128, d: u128) -> Option<Self> {
+        (d != 0 && GRANULARITY.is_multiple_of(d)).then(|| Self::fraction(n, d))
+    }
+
+    /// Takes `amount`, or everything if there is less, and returns what was taken.
+    pub(crate) fn take_up_to(&mut self, amount: Self) -> Self {
         if &amount <= self {
             *self -= amount;
             amount
@@ -44,55 +67,121 @@ impl<Q: QuantityType> Quantity<Q> {
         }
     }
 
+    /// Multiplies by a quantity of another type taken as a plain number, e.g. what one person
+    /// does by a number of people. Fractions of the result below the granularity are dropped.
+    pub fn scale<U: QuantityType>(self, factor: Quantity<U>) -> Self {
+        self.checked_scale(factor).expect(OVERFLOW)
+    }
+
+    pub fn checked_scale<U: QuantityType>(self, factor: Quantity<U>) -> Option<Self> {
+        // The fractional part of the factor is split so that no product overflows.
+        let (granules, remaining) = amount::div_rem(self.amount, GRANULARITY);
+        let whole_part = amount::checked_mul(granules, factor.residual)?;
+        let remaining_part = remaining * factor.residual;
+        let residual_part = self.residual * factor.residual / GRANULARITY;
+
+        let result = self
+            .checked_mul(amount::to_u128(factor.amount)?)?
+            .checked_add(Self {
+                amount: whole_part,
+                residual: 0,
+                _phantom: PhantomData,
+            })?
+            .checked_add(Self {
+                amount: amount::from_u128(remaining_part / GRANULARITY),
+                residual: remaining_part % GRANULARITY,
+                _phantom: PhantomData,
+            })?;
+        result.checked_increase_residual(residual_part)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self {
+            amount: amount::checked_add(self.amount, rhs.amount)?,
+            ..self
+        }
+        .checked_increase_residual(rhs.residual)
+    }
+
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or(Self::MAX)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        (rhs <= self).then(|| self - rhs)
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or_default()
+    }
+
+    pub fn checked_mul(self, rhs: u128) -> Option<Self> {
+        let multiples_of_granularity = rhs / GRANULARITY;
+        let rest = rhs % GRANULARITY;
+
+        // Both products are below `rhs` and `GRANULARITY * GRANULARITY`, so they fit.
+        let amount = amount::checked_add(
+            amount::checked_mul(self.amount, rhs)?,
+            amount::from_u128(self.residual * multiples_of_granularity),
+        )?;
+        Self {
+            amount,
+            residual: 0,
+            _phantom: PhantomData,
+        }
+        .checked_increase_residual(self.residual * rest)
+    }
+
+    pub fn saturating_mul(self, rhs: u128) -> Self {
+        self.checked_mul(rhs).unwrap_or(Self::MAX)
+    }
+
     pub(crate) fn as_f64(&self) -> f64 {
-        self.amount as f64 + (self.residual as f64) / GRANULARITY as f64
+        amount::to_f64(self.amount) + (self.residual as f64) / GRANULARITY as f64
     }
 }
 
 impl<Q: QuantityType> AddAssign for Quantity<Q> {
     fn add_assign(&mut self, rhs: Self) {
-        self.amount += rhs.amount;
-        self.increase_residual(rhs.residual);
+        *self = self.checked_add(rhs).expect(OVERFLOW);
     }
 }
 
 impl<Q: QuantityType> Mul<u128> for Quantity<Q> {
     type Output = Self;
 
-    fn mul(mut self, rhs: u128) -> Self::Output {
-        let multiples_of_granularity = rhs / GRANULARITY;
-        let rest = rhs % GRANULARITY;
-
-        let old_residual = self.residual;
-        self.residual = 0;
-
-        self.amount *= rhs;
-        self.amount += old_residual * multiples_of_granularity;
-
-        self.increase_residual(old_residual * rest);
-
-        self
+    fn mul(self, rhs: u128) -> Self::Output {
+        self.checked_mul(rhs).expect(OVERFLOW)
     }
 }
 
 pub trait QuantityType: Default + Debug + Copy + PartialEq {
-    fn strinigy(amount: u128, resdiual: u128, accuracy: usize) -> String;
+    const STYLE: format::Style;
 }
 
 pub mod types;
 
 pub mod signed {
-    use std::ops::{Mul, MulAssign};
-
-    use crate::duration::GRANULARITY;
+    use std::{
+        cmp::Ordering,
+        ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    };
 
-    use super::{Quantity, QuantityType};
+    use super::{format, Quantity, QuantityType};
 
+    /// A quantity with a sign, zero is never negative.
+    #[derive(Debug, Clone, Copy, PartialEq)]
     pub struct SignedQuantity<Q: QuantityType> {
         absolute_value: Quantity<Q>,
         is_not_negative: bool,
     }
 
+    impl<Q: QuantityType> Default for SignedQuantity<Q> {
+        fn default() -> Self {
+            Self::new(Quantity::default(), true)
+        }
+    }
+
     impl<Q: QuantityType> Mul<Quantity<Q>> for u128 {
         type Output = Quantity<Q>;
 
@@ -104,16 +193,7 @@ pub mod signed {
 
     impl<Q: QuantityType> MulAssign<u128> for Quantity<Q> {
         fn mul_assign(&mut self, rhs: u128) {
-            let small_part = rhs % GRANULARITY;
-            let big_part = rhs / GRANULARITY;
-
-            self.amount *= rhs;
-
-            let residual = self.residual;
-            self.residual = 0;
-
-            self.amount += big_part * residual;
-            self.increase_residual(small_part * residual);
+            *self = *self * rhs;
         }
     }
 
@@ -121,13 +201,124 @@ pub mod signed {
         pub fn new(absolute_value: Quantity<Q>, is_not_negative: bool) -> Self {
             Self {
                 absolute_value,
-                is_not_negative,
+                is_not_negative: is_not_negative || absolute_value == Quantity::default(),
             }
         }
 
-        pub fn stringify(&self, prec: usize) -> String {
+        pub fn is_negative(&self) -> bool {
+            !self.is_not_negative
+        }
+
+        pub fn absolute_value(&self) -> Quantity<Q> {
+            self.absolute_value
+        }
+
+        /// Negative values are rounded away from zero, so a deficit never looks smaller.
+        pub fn stringify(&self, prec: usize, notation: format::Notation) -> String {
             let sign_char = if self.is_not_negative { '+' } else { '-' };
-            format!("{}{}", sign_char, self.absolute_value.stringify(prec))
+            let absolute_value =
+                format::format(self.absolute_value, prec, self.is_negative(), notation);
+            format!("{}{}", sign_char, absolute_value)
+        }
+    }
+
+    impl<Q: QuantityType> From<Quantity<Q>> for SignedQuantity<Q> {
+        fn from(value: Quantity<Q>) -> Self {
+            Self::new(value, true)
+        }
+    }
+
+    impl<Q: QuantityType> Neg for SignedQuantity<Q> {
+        type Output = Self;
+
+        fn neg(self) -> Self::Output {
+            Self::new(self.absolute_value, !self.is_not_negative)
+        }
+    }
+
+    impl<Q: QuantityType> Add for SignedQuantity<Q> {
+        type Output = Self;
+
+        fn add(self, rhs: Self) -> Self::Output {
+            if self.is_not_negative == rhs.is_not_negative {
+                Self::new(
+                    self.absolute_value + rhs.absolute_value,
+                    self.is_not_negative,
+                )
+            } else if rhs.absolute_value <= self.absolute_value {
+                Self::new(
+                    self.absolute_value - rhs.absolute_value,
+                    self.is_not_negative,
+                )
+            } else {
+                Self::new(
+                    rhs.absolute_value - self.absolute_value,
+                    rhs.is_not_negative,
+                )
+            }
+        }
+    }
+
+    impl<Q: QuantityType> Sub for SignedQuantity<Q> {
+        type Output = Self;
+
+        fn sub(self, rhs: Self) -> Self::Output {
+            self + -rhs
+        }
+    }
+
+    impl<Q: QuantityType> Add<Quantity<Q>> for SignedQuantity<Q> {
+        type Output = Self;
+
+        fn add(self, rhs: Quantity<Q>) -> Self::Output {
+            self + Self::from(rhs)
+        }
+    }
+
+    impl<Q: QuantityType> Sub<Quantity<Q>> for SignedQuantity<Q> {
+        type Output = Self;
+
+        fn sub(self, rhs: Quantity<Q>) -> Self::Output {
+            self - Self::from(rhs)
+        }
+    }
+
+    impl<Q: QuantityType> AddAssign for SignedQuantity<Q> {
+        fn add_assign(&mut self, rhs: Self) {
+            *self = *self + rhs;
+        }
+    }
+
+    impl<Q: QuantityType> SubAssign for SignedQuantity<Q> {
+        fn sub_assign(&mut self, rhs: Self) {
+            *self = *self - rhs;
+        }
+    }
+
+    impl<Q: QuantityType> Mul<u128> for SignedQuantity<Q> {
+        type Output = Self;
+
+        fn mul(self, rhs: u128) -> Self::Output {
+            Self::new(self.absolute_value * rhs, self.is_not_negative)
+        }
+    }
+
+    impl<Q: QuantityType> Mul<SignedQuantity<Q>> for u128 {
+        type Output = SignedQuantity<Q>;
+
+        fn mul(self, rhs: SignedQuantity<Q>) -> Self::Output {
+            rhs * self
+        }
+    }
+
+    impl<Q: QuantityType> PartialOrd for SignedQuantity<Q> {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            match (self.is_not_negative, other.is_not_negative) {
+                (true, true) => self.absolute_value.partial_cmp(&other.absolute_value),
+                (false, false) => other.absolute_value.partial_cmp(&self.absolute_value),
+                (true, false) => Some(Ordering::Greater),
+                (false, true) => Some(Ordering::Less),
+            }
         }
     }
 
@@ -138,23 +329,47 @@ pub mod signed {
     }
 
     impl<Q: QuantityType> PartialOrd<Quantity<Q>> for SignedQuantity<Q> {
-        fn partial_cmp(&self, other: &Quantity<Q>) -> Option<std::cmp::Ordering> {
-            if self.is_not_negative {
-                self.absolute_value.partial_cmp(other)
-            } else {
-                Some(std::cmp::Ordering::Less)
-            }
+        fn partial_cmp(&self, other: &Quantity<Q>) -> Option<Ordering> {
+            self.partial_cmp(&Self::from(*other))
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "", try_from = "UncheckedQuantity<Q>")]
 pub struct Quantity<Q: QuantityType> {
-    amount: u128,
+    amount: Amount,
     residual: u128,
     _phantom: PhantomData<Q>,
 }
 
+/// A loaded quantity, before its residual is known to be below [`GRANULARITY`].
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct UncheckedQuantity<Q: QuantityType> {
+    amount: Amount,
+    residual: u128,
+    _phantom: PhantomData<Q>,
+}
+
+impl<Q: QuantityType> TryFrom<UncheckedQuantity<Q>> for Quantity<Q> {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedQuantity<Q>) -> Result<Self, Self::Error> {
+        if unchecked.residual >= GRANULARITY {
+            return Err(format!(
+                "residual {} of a quantity is not below {GRANULARITY}",
+                unchecked.residual
+            ));
+        }
+        Ok(Self {
+            amount: unchecked.amount,
+            residual: unchecked.residual,
+            _phantom: PhantomData,
+        })
+    }
+}
+
 impl<Q: QuantityType> Add for Quantity<Q> {
     type Output = Self;
 
@@ -175,7 +390,7 @@ impl<Q: QuantityType> Sub for Quantity<Q> {
 
 impl<Q: QuantityType> PartialOrd for Quantity<Q> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        fn to_tuple<P: QuantityType>(s: &Quantity<P>) -> (u128, u128) {
+        fn to_tuple<P: QuantityType>(s: &Quantity<P>) -> (Amount, u128) {
             (s.amount, s.residual)
         }
 
@@ -192,29 +407,37 @@ impl<Q: QuantityType> SubAssign for Quantity<Q> {
             (1, GRANULARITY + self.residual - rhs.residual)
         };
 
-        self.amount -= rhs.amount + spillover;
+        self.amount = amount::checked_sub(self.amount, rhs.amount)
+            .and_then(|amount| amount::checked_sub(amount, amount::from_u128(spillover)))
+            .expect("quantities cannot become negative");
         self.residual = res;
     }
 }
 
 impl<Q: QuantityType> Quantity<Q> {
-    pub fn stringify(&self, accuracy: usize) -> String {
-        Q::strinigy(self.amount, self.residual, accuracy)
+    /// Rounded down, in the unit and notation that fit best.
+    pub fn stringify(&self, accuracy: usize, notation: format::Notation) -> String {
+        format::format(*self, accuracy, false, notation)
     }
 
+    /// Whole amounts beyond `u128` are cut off at its maximum.
     pub fn whole_amount(&self) -> u128 {
-        self.amount
+        amount::to_u128(self.amount).unwrap_or(u128::MAX)
     }
 
     pub const fn divide_with_remainder(mut self, divisor: u128) -> (Self, u128) {
         assert!(divisor != 0);
 
-        let amount_not_accounted_for = self.amount % divisor;
-        self.amount /= divisor;
-        self.residual += amount_not_accounted_for * GRANULARITY;
+        let (amount, amount_not_accounted_for) = amount::div_rem(self.amount, divisor);
+        self.amount = amount;
 
-        let residual_not_accounted_for = self.residual % divisor;
-        self.residual /= divisor;
+        // As `amount_not_accounted_for` is below `divisor`, the new residual is below
+        // `GRANULARITY`, but the numerator may need more than 128 bits.
+        let (high, low) = amount::mul_wide(amount_not_accounted_for, GRANULARITY);
+        let (low, carry) = low.overflowing_add(self.residual);
+        let (residual, residual_not_accounted_for) =
+            amount::div_wide(high + carry as u128, low, divisor);
+        self.residual = residual;
 
         assert!(self.residual < GRANULARITY);
 
@@ -230,7 +453,7 @@ impl<Q: QuantityType> Quantity<Q> {
 
     pub const fn new(amount: u128) -> Self {
         Self {
-            amount,
+            amount: amount::from_u128(amount),
             residual: 0,
             _phantom: PhantomData,
         }
@@ -248,6 +471,98 @@ impl<Q: QuantityType> Quantity<Q> {
 
 impl<Q: QuantityType> AddAssign<u128> for Quantity<Q> {
     fn add_assign(&mut self, rhs: u128) {
-        self.amount += rhs;
+        *self += Self::new(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::{Duration, GRANULARITY},
+        world::rate::Rate,
+    };
+
+    use super::{
+        format::Notation,
+        types::{Emission, Person},
+        Quantity,
+    };
+
+    const TEN_TO_THE_30: u128 = 1_000_000_000_000_000_000_000_000_000_000;
+
+    #[test]
+    fn year_scale_deltas_are_exact() {
+        let world_emission: Rate<Emission> =
+            Rate::new(Quantity::new(38_450_000_000_000_000), Duration::YEAR);
+        let millennia = 10_000 * Duration::YEAR;
+
+        let emitted = world_emission * millennia;
+        assert_eq!(emitted, Quantity::new(384_500_000_000_000_000_000));
+        assert_eq!(emitted / millennia, world_emission);
+        assert_eq!(
+            world_emission.checked_mul(millennia),
+            Some(Quantity::new(384_500_000_000_000_000_000))
+        );
+    }
+
+    #[test]
+    fn huge_amounts_keep_their_fractions() {
+        let huge: Quantity<Emission> = Quantity::new(TEN_TO_THE_30) + Quantity::fraction(1, 2);
+
+        assert_eq!(
+            huge * 3,
+            Quantity::new(3 * TEN_TO_THE_30 + 1) + Quantity::fraction(1, 2)
+        );
+        assert_eq!(
+            huge.scale(Quantity::<Person>::fraction(1, 4)),
+            Quantity::new(TEN_TO_THE_30 / 4) + Quantity::fraction(1, 8)
+        );
+        let (third, remainder) = huge.divide_with_remainder(3);
+        assert_eq!(remainder, 0);
+        assert_eq!(third * 3, huge);
+        assert_eq!(
+            huge.stringify(0, Notation::Units),
+            "1,000,000,000,000,000Gt"
+        );
+    }
+
+    #[test]
+    fn overflows_are_caught() {
+        let huge: Quantity<Emission> = Quantity::new(TEN_TO_THE_30);
+
+        assert_eq!(
+            Quantity::<Emission>::MAX.saturating_add(huge),
+            Quantity::MAX
+        );
+        assert_eq!(Quantity::<Emission>::MAX.checked_add(huge), None);
+        assert_eq!(huge.checked_sub(huge * 2), None);
+        assert_eq!(huge.saturating_sub(huge * 2), Quantity::default());
+
+        let mut stock = huge;
+        assert_eq!(stock.take_up_to(huge * 2), huge);
+        assert_eq!(stock, Quantity::default());
+        assert_eq!(Duration::MAX.checked_add(Duration::TICK), None);
+        assert_eq!(Duration::MAX.saturating_mul(2), Duration::MAX);
+
+        // Beyond `u128`, only the big backing has room.
+        let beyond = huge.checked_mul(1_000_000_000);
+        if cfg!(feature = "big-quantities") {
+            let beyond = beyond.unwrap();
+            assert_eq!(beyond.whole_amount(), u128::MAX);
+            assert_eq!(beyond.divide_exactly(1_000_000_000), huge);
+        } else {
+            assert_eq!(beyond, None);
+            assert_eq!(huge.saturating_mul(1_000_000_000), Quantity::MAX);
+        }
+    }
+
+    #[test]
+    fn loading_rejects_residuals_beyond_granularity() {
+        let half: Quantity<Emission> = Quantity::new(5) + Quantity::fraction(1, 2);
+        let saved = ron::to_string(&half).unwrap();
+        assert_eq!(ron::from_str::<Quantity<Emission>>(&saved).unwrap(), half);
+
+        let corrupt = format!("(amount: 5, residual: {GRANULARITY}, _phantom: ())");
+        assert!(ron::from_str::<Quantity<Emission>>(&corrupt).is_err());
     }
 }
@@ -0,0 +1,219 @@
+#[cfg(not(feature = "big-quantities"))]
+pub use self::narrow::*;
+#[cfg(feature = "big-quantities")]
+pub use self::wide::*;
+
+const LOW_BITS: u128 = u64::MAX as u128;
+
+/// The full product of `a` and `b`, as (high, low) words.
+pub const fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    let (a_high, a_low) = (a >> 64, a & LOW_BITS);
+    let (b_high, b_low) = (b >> 64, b & LOW_BITS);
+
+    let low_low = a_low * b_low;
+    let low_high = a_low * b_high;
+    let high_low = a_high * b_low;
+    let high_high = a_high * b_high;
+
+    let middle = (low_low >> 64) + (low_high & LOW_BITS) + (high_low & LOW_BITS);
+    let low = (low_low & LOW_BITS) | (middle << 64);
+    let high = high_high + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+    (high, low)
+}
+
+/// Divides (high, low) words by `divisor`, into quotient and remainder.
+/// The quotient has to fit, so `high` has to be smaller than `divisor`.
+pub const fn div_wide(high: u128, low: u128, divisor: u128) -> (u128, u128) {
+    assert!(high < divisor);
+    if high == 0 {
+        return (low / divisor, low % divisor);
+    }
+
+    let mut remainder = high;
+    let mut quotient = 0;
+    let mut bit = 0;
+    while bit < 128 {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> (127 - bit)) & 1);
+        quotient <<= 1;
+        if carry == 1 || divisor <= remainder {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient |= 1;
+        }
+        bit += 1;
+    }
+    (quotient, remainder)
+}
+
+#[cfg(not(feature = "big-quantities"))]
+mod narrow {
+    pub type Amount = u128;
+
+    pub const MAX: Amount = u128::MAX;
+
+    pub const fn from_u128(value: u128) -> Amount {
+        value
+    }
+
+    pub const fn to_u128(amount: Amount) -> Option<u128> {
+        Some(amount)
+    }
+
+    pub fn to_f64(amount: Amount) -> f64 {
+        amount as f64
+    }
+
+    pub const fn checked_add(a: Amount, b: Amount) -> Option<Amount> {
+        a.checked_add(b)
+    }
+
+    pub const fn checked_sub(a: Amount, b: Amount) -> Option<Amount> {
+        a.checked_sub(b)
+    }
+
+    pub const fn checked_mul(a: Amount, b: u128) -> Option<Amount> {
+        a.checked_mul(b)
+    }
+
+    pub const fn div_rem(a: Amount, divisor: u128) -> (Amount, u128) {
+        (a / divisor, a % divisor)
+    }
+}
+
+#[cfg(feature = "big-quantities")]
+mod wide {
+    use std::fmt;
+
+    use serde::{
+        de::{self, SeqAccess, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::{div_wide, mul_wide};
+
+    /// An unsigned 256 bit number.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Amount {
+        high: u128,
+        low: u128,
+    }
+
+    pub const MAX: Amount = Amount {
+        high: u128::MAX,
+        low: u128::MAX,
+    };
+
+    pub const fn from_u128(value: u128) -> Amount {
+        Amount {
+            high: 0,
+            low: value,
+        }
+    }
+
+    pub const fn to_u128(amount: Amount) -> Option<u128> {
+        if amount.high == 0 {
+            Some(amount.low)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(amount: Amount) -> f64 {
+        amount.high as f64 * 2_f64.powi(128) + amount.low as f64
+    }
+
+    pub const fn checked_add(a: Amount, b: Amount) -> Option<Amount> {
+        let (low, carry) = a.low.overflowing_add(b.low);
+        let Some(high) = a.high.checked_add(b.high) else {
+            return None;
+        };
+        let Some(high) = high.checked_add(carry as u128) else {
+            return None;
+        };
+        Some(Amount { high, low })
+    }
+
+    pub const fn checked_sub(a: Amount, b: Amount) -> Option<Amount> {
+        let (low, borrow) = a.low.overflowing_sub(b.low);
+        let Some(high) = a.high.checked_sub(b.high) else {
+            return None;
+        };
+        let Some(high) = high.checked_sub(borrow as u128) else {
+            return None;
+        };
+        Some(Amount { high, low })
+    }
+
+    pub const fn checked_mul(a: Amount, b: u128) -> Option<Amount> {
+        let (carry, low) = mul_wide(a.low, b);
+        let Some(high) = a.high.checked_mul(b) else {
+            return None;
+        };
+        let Some(high) = high.checked_add(carry) else {
+            return None;
+        };
+        Some(Amount { high, low })
+    }
+
+    pub const fn div_rem(a: Amount, divisor: u128) -> (Amount, u128) {
+        let high = a.high / divisor;
+        let (low, remainder) = div_wide(a.high % divisor, a.low, divisor);
+        (Amount { high, low }, remainder)
+    }
+
+    /// Amounts that fit into `u128` are saved like without this feature.
+    impl Serialize for Amount {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match to_u128(*self) {
+                Some(value) => serializer.serialize_u128(value),
+                None => (self.high, self.low).serialize(serializer),
+            }
+        }
+    }
+
+    struct AmountVisitor;
+
+    impl<'de> Visitor<'de> for AmountVisitor {
+        type Value = Amount;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an unsigned integer or a pair of them")
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Amount, E> {
+            Ok(from_u128(value.into()))
+        }
+
+        fn visit_u128<E: de::Error>(self, value: u128) -> Result<Amount, E> {
+            Ok(from_u128(value))
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Amount, E> {
+            u128::try_from(value)
+                .map(from_u128)
+                .map_err(|_| E::custom("negative amount"))
+        }
+
+        fn visit_i128<E: de::Error>(self, value: i128) -> Result<Amount, E> {
+            u128::try_from(value)
+                .map(from_u128)
+                .map_err(|_| E::custom("negative amount"))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Amount, A::Error> {
+            let high = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let low = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            Ok(Amount { high, low })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Amount {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(AmountVisitor)
+        }
+    }
+}
@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use super::{signed::SignedQuantity, Quantity, QuantityType};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct Balance<Q: QuantityType> {
     pos: Quantity<Q>,
     neg: Quantity<Q>,
@@ -17,17 +20,19 @@ impl<Q: QuantityType> Balance<Q> {
         SignedQuantity::new(bigger - smaller, is_not_negative)
     }
 
-    pub fn pos_mut(&mut self) -> &mut Quantity<Q> {
-        &mut self.pos
+    /// Adds to the balance, counted in [`Self::pos`].
+    pub fn credit(&mut self, amount: Quantity<Q>) {
+        self.pos = self.pos.saturating_add(amount);
     }
 
-    pub fn neg_mut(&mut self) -> &mut Quantity<Q> {
-        &mut self.neg
+    /// Takes from the balance, it may become negative.
+    pub fn debit(&mut self, amount: Quantity<Q>) {
+        self.neg = self.neg.saturating_add(amount);
     }
 
-    // pub fn pos(&self) -> &Resource {
-    //     &self.pos
-    // }
+    pub fn pos(&self) -> &Quantity<Q> {
+        &self.pos
+    }
 
     // pub fn neg(&self) -> &Resource {
     //     &self.neg
@@ -0,0 +1,222 @@
+use super::{Quantity, QuantityType};
+
+pub struct Style {
+    /// Written before the number, like a currency symbol.
+    prefix: &'static str,
+    /// Units by their power of ten, starting with the base unit.
+    units: &'static [(u32, &'static str)],
+    /// Whether trailing zeros of the decimals are written, like for cents.
+    fixed_decimals: bool,
+}
+
+pub const NUMBER: Style = Style {
+    prefix: "",
+    units: &[(0, ""), (6, "M"), (9, "B"), (12, "T")],
+    fixed_decimals: false,
+};
+
+pub const EMISSION: Style = Style {
+    prefix: "",
+    units: &[
+        (0, "g"),
+        (3, "kg"),
+        (6, "t"),
+        (9, "kt"),
+        (12, "Mt"),
+        (15, "Gt"),
+    ],
+    fixed_decimals: false,
+};
+
+pub const MONEY: Style = Style {
+    prefix: "$",
+    units: &[(0, ""), (6, "M"), (9, "B")],
+    fixed_decimals: true,
+};
+
+/// Chosen by the player, the same for every quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Notation {
+    /// Scaled to the largest unit that fits, e.g. 1,234.5kg.
+    #[default]
+    Units,
+    /// Base unit with an exponent that is a multiple of three, e.g. 12.34e6g.
+    Engineering,
+    /// Base unit with one digit before the point, e.g. 1.23e7g.
+    Scientific,
+}
+
+impl Notation {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "units" => Some(Self::Units),
+            "engineering" => Some(Self::Engineering),
+            "scientific" => Some(Self::Scientific),
+            _ => None,
+        }
+    }
+}
+
+/// Values beyond the base unit keep at least this many decimals, so 1,234,567 people are not
+/// just 1M.
+const SCALED_DECIMALS: usize = 2;
+
+/// Values below this are written without an exponent in every notation.
+const SMALLEST_WITH_EXPONENT: u128 = 1_000;
+
+/// `10^exponent`, at most [`u128::MAX`].
+fn power_of_ten(exponent: u32) -> u128 {
+    10_u128.checked_pow(exponent).unwrap_or(u128::MAX)
+}
+
+/// Writes `quantity` with `accuracy` decimals in its base unit. `round_up` is for negative
+/// values, whose magnitude must not shrink by rounding.
+pub(super) fn format<Q: QuantityType>(
+    quantity: Quantity<Q>,
+    accuracy: usize,
+    round_up: bool,
+    notation: Notation,
+) -> String {
+    let style = &Q::STYLE;
+    let (base_exponent, base_unit) = style.units[0];
+    let whole = quantity.whole_amount();
+    let digits = whole.checked_ilog10().unwrap_or(0);
+
+    let (exponent, unit) = match notation {
+        Notation::Units => style
+            .units
+            .iter()
+            .rev()
+            .find(|&&(exponent, _)| power_of_ten(exponent) <= whole)
+            .map_or((base_exponent, base_unit), |&(exponent, unit)| {
+                (exponent, unit)
+            }),
+        _ if whole < SMALLEST_WITH_EXPONENT => (base_exponent, base_unit),
+        Notation::Engineering => (digits - digits % 3, base_unit),
+        Notation::Scientific => (digits, base_unit),
+    };
+    let decimals = if exponent == base_exponent {
+        accuracy
+    } else {
+        accuracy.max(SCALED_DECIMALS)
+    };
+
+    let scaled = scaled_digits(quantity, exponent, decimals as u32, round_up);
+    let number = write_decimal(
+        scaled,
+        decimals,
+        style.fixed_decimals,
+        notation == Notation::Units,
+    );
+    let exponent = if notation == Notation::Units || exponent == base_exponent {
+        String::new()
+    } else {
+        format!("e{exponent}")
+    };
+    format!("{}{number}{exponent}{unit}", style.prefix)
+}
+
+/// `quantity * 10^(decimals - exponent)` as a whole number, rounded as asked.
+fn scaled_digits<Q: QuantityType>(
+    quantity: Quantity<Q>,
+    exponent: u32,
+    decimals: u32,
+    round_up: bool,
+) -> u128 {
+    let (scaled, remainder) = quantity
+        .saturating_mul(power_of_ten(decimals))
+        .divide_with_remainder(power_of_ten(exponent));
+    let digits = scaled.whole_amount();
+    if round_up && (remainder != 0 || scaled.residual != 0) {
+        digits.saturating_add(1)
+    } else {
+        digits
+    }
+}
+
+/// Writes `value / 10^decimals`, optionally with thousands separators.
+fn write_decimal(value: u128, decimals: usize, fixed_decimals: bool, separators: bool) -> String {
+    let factor = power_of_ten(decimals as u32);
+    let whole = (value / factor).to_string();
+
+    let mut number = String::new();
+    for (index, digit) in whole.chars().enumerate() {
+        if separators && index != 0 && (whole.len() - index).is_multiple_of(3) {
+            number.push(',');
+        }
+        number.push(digit);
+    }
+
+    let mut fraction = format!("{:0decimals$}", value % factor);
+    if decimals == 0 {
+        fraction.clear();
+    }
+    if !fixed_decimals {
+        fraction.truncate(fraction.trim_end_matches('0').len());
+    }
+    if !fraction.is_empty() {
+        number.push('.');
+        number.push_str(&fraction);
+    }
+    number
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::world::quantity::{
+        signed::SignedQuantity,
+        types::{Emission, Money, Person},
+        Quantity,
+    };
+
+    use super::{format, Notation};
+
+    fn emission(grams: u128) -> Quantity<Emission> {
+        Quantity::new(grams)
+    }
+
+    #[test]
+    fn emissions_scale_to_fitting_units() {
+        let write = |quantity, accuracy| format(quantity, accuracy, false, Notation::Units);
+        assert_eq!(write(emission(999), 0), "999g");
+        assert_eq!(write(emission(1_500), 0), "1.5kg");
+        assert_eq!(write(emission(123_456_789), 2), "123.45t");
+        assert_eq!(write(emission(38_450_000_000_000_000), 1), "38.45Gt");
+        assert_eq!(write(emission(1_234_000_000_000_000_000), 0), "1,234Gt");
+        assert_eq!(write(Quantity::fraction(1, 8), 4), "0.125g");
+    }
+
+    #[test]
+    fn numbers_get_separators_and_money_keeps_cents() {
+        let people: Quantity<Person> = Quantity::new(987_654);
+        assert_eq!(format(people, 0, false, Notation::Units), "987,654");
+        let people: Quantity<Person> = Quantity::new(9_000_000_000);
+        assert_eq!(format(people, 0, false, Notation::Units), "9B");
+
+        let money: Quantity<Money> = Quantity::new(1_234) + Quantity::fraction(1, 2);
+        assert_eq!(format(money, 2, false, Notation::Units), "$1,234.50");
+    }
+
+    #[test]
+    fn notations_use_exponents() {
+        let value = emission(12_345_678);
+        assert_eq!(format(value, 2, false, Notation::Engineering), "12.34e6g");
+        assert_eq!(format(value, 2, false, Notation::Scientific), "1.23e7g");
+        assert_eq!(
+            format(emission(999), 0, false, Notation::Scientific),
+            "999g"
+        );
+    }
+
+    #[test]
+    fn rounding_never_overstates_savings() {
+        let saved = emission(1_999);
+        assert_eq!(format(saved, 0, false, Notation::Units), "1.99kg");
+
+        let deficit = SignedQuantity::new(emission(1_999), false);
+        assert_eq!(deficit.stringify(0, Notation::Units), "-2kg");
+        let deficit: SignedQuantity<Emission> =
+            SignedQuantity::new(Quantity::fraction(1, 3), false);
+        assert_eq!(deficit.stringify(2, Notation::Units), "-0.34g");
+    }
+}
@@ -1,17 +1,9 @@
 use std::fmt::Debug;
 
-use crate::duration::GRANULARITY;
-
-use super::{Quantity, QuantityType};
-
-fn get_conservative_f64(amount: u128, residual: u128, accuracy: usize) -> f64 {
-    let accuracy_factor = 10_u128.pow(accuracy as u32);
-
-    let res: Quantity<Flyer> =
-        accuracy_factor * (Quantity::new(amount) + Quantity::fraction(residual, GRANULARITY));
-
-    res.whole_amount() as f64 / accuracy_factor as f64
-}
+use super::{
+    format::{self, Style},
+    QuantityType,
+};
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub struct Emission;
@@ -19,9 +11,7 @@ pub struct Emission;
 pub trait Number: Default + Debug + Copy + PartialEq {}
 
 impl<N: Number> QuantityType for N {
-    fn strinigy(amount: u128, resdiual: u128, accuracy: usize) -> String {
-        format!("{}", get_conservative_f64(amount, resdiual, accuracy))
-    }
+    const STYLE: Style = format::NUMBER;
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
@@ -34,12 +24,16 @@ impl Number for Flyer {}
 impl Number for Person {}
 
 impl QuantityType for Emission {
-    fn strinigy(amount: u128, residual: u128, accuracy: usize) -> String {
-        let f64 = get_conservative_f64(amount, residual, accuracy);
-        format!("{}g", f64)
-    }
+    const STYLE: Style = format::EMISSION;
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub struct ResearchPoints;
 impl Number for ResearchPoints {}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Money;
+
+impl QuantityType for Money {
+    const STYLE: Style = format::MONEY;
+}
@@ -1,10 +1,20 @@
-use std::ops::{AddAssign, Mul, MulAssign};
+use std::{
+    cmp::Ordering,
+    marker::PhantomData,
+    ops::{AddAssign, Div, Mul, MulAssign, Neg, Sub},
+};
+
+use serde::{Deserialize, Serialize};
 
 use crate::duration::Duration;
 
-use super::{quantity::QuantityType, Quantity};
+use super::{
+    quantity::{format::Notation, signed::SignedQuantity, QuantityType},
+    Quantity,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct Rate<Q: QuantityType> {
     difference_per_tick: Quantity<Q>,
 }
@@ -23,12 +33,77 @@ impl<Q: QuantityType> Rate<Q> {
         }
     }
 
+    /// Like [`Self::new`], but `None` instead of a panic if the increase is not exactly divisible.
+    pub fn checked_new(increase: Quantity<Q>, duration: Duration) -> Option<Self> {
+        let (difference_per_tick, remainder) = increase.divide_with_remainder(duration.ticks());
+        (remainder == 0).then_some(Self {
+            difference_per_tick,
+        })
+    }
+
     pub fn per(self, duration: Duration) -> Quantity<Q> {
         self * duration
     }
 
-    pub fn stringify(&self, accuracy: usize) -> String {
-        format!("{}/s", self.per(Duration::SECOND).stringify(accuracy))
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(Self {
+            difference_per_tick: self
+                .difference_per_tick
+                .checked_add(rhs.difference_per_tick)?,
+        })
+    }
+
+    /// What accumulates over `duration`, `None` if it is too much to hold.
+    pub fn checked_mul(self, duration: Duration) -> Option<Quantity<Q>> {
+        self.difference_per_tick.checked_mul(duration.ticks())
+    }
+
+    /// What accumulates over `duration`, at most [`Quantity::MAX`].
+    pub fn saturating_mul(self, duration: Duration) -> Quantity<Q> {
+        self.difference_per_tick.saturating_mul(duration.ticks())
+    }
+
+    /// What producing at this rate costs if every unit costs `price`.
+    pub fn cost_at<U: QuantityType>(self, price: Quantity<U>) -> Rate<U> {
+        Rate {
+            difference_per_tick: price.scale(self.difference_per_tick),
+        }
+    }
+
+    pub fn stringify(&self, accuracy: usize, notation: Notation) -> String {
+        format!(
+            "{}/s",
+            self.per(Duration::SECOND).stringify(accuracy, notation)
+        )
+    }
+}
+
+impl<Q: QuantityType> PartialOrd for Rate<Q> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.difference_per_tick
+            .partial_cmp(&other.difference_per_tick)
+    }
+}
+
+/// Spreads a quantity evenly over a duration, unlike [`Rate::new`] rounding down what does not
+/// divide exactly.
+impl<Q: QuantityType> Div<Duration> for Quantity<Q> {
+    type Output = Rate<Q>;
+
+    fn div(self, rhs: Duration) -> Self::Output {
+        let (difference_per_tick, _) = self.divide_with_remainder(rhs.ticks());
+        Rate {
+            difference_per_tick,
+        }
+    }
+}
+
+/// How many times faster one rate is than the other.
+impl<Q: QuantityType> Div for Rate<Q> {
+    type Output = f64;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.difference_per_tick.as_f64() / rhs.difference_per_tick.as_f64()
     }
 }
 
@@ -54,3 +129,188 @@ impl<Q: QuantityType> Mul<Rate<Q>> for u128 {
         rhs
     }
 }
+
+/// A rate that may be negative, like the net emissions of gains and spending.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SignedRate<Q: QuantityType> {
+    difference_per_tick: SignedQuantity<Q>,
+}
+
+impl<Q: QuantityType> SignedRate<Q> {
+    pub fn per(self, duration: Duration) -> SignedQuantity<Q> {
+        self * duration
+    }
+
+    pub fn stringify(&self, accuracy: usize, notation: Notation) -> String {
+        format!(
+            "{}/s",
+            self.per(Duration::SECOND).stringify(accuracy, notation)
+        )
+    }
+}
+
+impl<Q: QuantityType> From<Rate<Q>> for SignedRate<Q> {
+    fn from(rate: Rate<Q>) -> Self {
+        Self {
+            difference_per_tick: rate.difference_per_tick.into(),
+        }
+    }
+}
+
+impl<Q: QuantityType> Sub for Rate<Q> {
+    type Output = SignedRate<Q>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        SignedRate::from(self) - SignedRate::from(rhs)
+    }
+}
+
+impl<Q: QuantityType> Sub for SignedRate<Q> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            difference_per_tick: self.difference_per_tick - rhs.difference_per_tick,
+        }
+    }
+}
+
+impl<Q: QuantityType> Neg for SignedRate<Q> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            difference_per_tick: -self.difference_per_tick,
+        }
+    }
+}
+
+impl<Q: QuantityType> PartialOrd for SignedRate<Q> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.difference_per_tick
+            .partial_cmp(&other.difference_per_tick)
+    }
+}
+
+impl<Q: QuantityType> Mul<Duration> for SignedRate<Q> {
+    type Output = SignedQuantity<Q>;
+
+    fn mul(self, rhs: Duration) -> Self::Output {
+        self.difference_per_tick * rhs.ticks()
+    }
+}
+
+/// A rate for every unit of `U`, like the emissions a single supporter saves.
+/// Multiplied by a quantity of `U`, fractions included, it gives the total rate.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(bound = "", transparent)]
+pub struct RatePerUnit<Q: QuantityType, U: QuantityType> {
+    rate: Rate<Q>,
+    #[serde(skip)]
+    _unit: PhantomData<U>,
+}
+
+impl<Q: QuantityType, U: QuantityType> RatePerUnit<Q, U> {
+    pub const fn new(rate: Rate<Q>) -> Self {
+        Self {
+            rate,
+            _unit: PhantomData,
+        }
+    }
+
+    /// The rate of a single unit.
+    pub fn rate(self) -> Rate<Q> {
+        self.rate
+    }
+}
+
+impl<Q: QuantityType, U: QuantityType> PartialOrd for RatePerUnit<Q, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.rate.partial_cmp(&other.rate)
+    }
+}
+
+impl<Q: QuantityType, U: QuantityType> AddAssign for RatePerUnit<Q, U> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.rate += rhs.rate;
+    }
+}
+
+impl<Q: QuantityType, U: QuantityType> Mul<RatePerUnit<Q, U>> for u128 {
+    type Output = RatePerUnit<Q, U>;
+
+    fn mul(self, rhs: RatePerUnit<Q, U>) -> Self::Output {
+        RatePerUnit::new(self * rhs.rate)
+    }
+}
+
+impl<Q: QuantityType, U: QuantityType> Mul<Quantity<U>> for RatePerUnit<Q, U> {
+    type Output = Rate<Q>;
+
+    fn mul(self, rhs: Quantity<U>) -> Self::Output {
+        Rate {
+            difference_per_tick: self.rate.difference_per_tick.scale(rhs),
+        }
+    }
+}
+
+impl<Q: QuantityType, U: QuantityType> Mul<RatePerUnit<Q, U>> for Quantity<U> {
+    type Output = Rate<Q>;
+
+    fn mul(self, rhs: RatePerUnit<Q, U>) -> Self::Output {
+        rhs * self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        duration::Duration,
+        world::quantity::{
+            format::Notation,
+            types::{Emission, Person},
+            Quantity,
+        },
+    };
+
+    use super::{Rate, RatePerUnit, SignedRate};
+
+    fn per_second(grams: u128) -> Rate<Emission> {
+        Rate::new(Quantity::new(grams), Duration::SECOND)
+    }
+
+    #[test]
+    fn quantities_divide_into_rates() {
+        let rate = Quantity::<Emission>::new(120) / Duration::MINUTE;
+        assert_eq!(rate, Rate::new(Quantity::new(2), Duration::SECOND));
+        assert_eq!(rate / Rate::new(Quantity::new(1), Duration::SECOND), 2.0);
+    }
+
+    #[test]
+    fn rates_per_unit_take_fractions() {
+        let per_person: RatePerUnit<Emission, Person> =
+            RatePerUnit::new(Rate::new(Quantity::new(4), Duration::SECOND));
+        let people = Quantity::new(1_000_000_000) + Quantity::fraction(3, 4);
+        assert_eq!(
+            (per_person * people).per(Duration::SECOND),
+            Quantity::new(4_000_000_003)
+        );
+    }
+
+    #[test]
+    fn rates_subtract_into_signed_rates() {
+        let net = per_second(2) - per_second(5);
+        assert!(net.per(Duration::MINUTE).is_negative());
+        assert_eq!(net.stringify(0, Notation::Units), "-3g/s");
+        assert_eq!((-net).per(Duration::MINUTE), Quantity::new(180));
+        assert!(net < per_second(1) - per_second(1));
+    }
+
+    #[test]
+    fn default_signed_rates_are_zero() {
+        let zero = SignedRate::<Emission>::default();
+        assert!(!zero.per(Duration::MINUTE).is_negative());
+        assert_eq!(zero, per_second(1) - per_second(1));
+        assert_eq!(zero.stringify(0, Notation::Units), "+0g/s");
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     grid::{text::Text, Cell, Color, Grid, MutGridView},
-    input::{Event, Input, Key},
+    input::{Event, Input},
 };
 
 use super::World;
@@ -15,7 +15,7 @@ pub const CHARS_CARD: usize = 23;
 pub const CHARS_GRID: usize = CHARS_MENU + CHARS_CARD + 3;
 
 impl World {
-    fn render_bottom_area(&mut self, input: &Input, mut view: MutGridView<'_, Cell>) {
+    fn render_bottom_area(&self, input: &Input, mut view: MutGridView<'_, Cell>) {
         assert_eq!(view.height(), LINES_MESSAGES);
         assert_eq!(view.width(), CHARS_GRID);
 
@@ -43,30 +43,33 @@ impl World {
         }
     }
 
-    fn render_main_navigation(&mut self, input: &Input, mut view: MutGridView<'_, Cell>) {
+    fn render_main_navigation(&self, mut view: MutGridView<'_, Cell>) {
         assert_eq!(view.height(), LINES_MAIN_FRAME);
         assert_eq!(view.width(), CHARS_GRID);
 
         let (left, pre_right) = view.split_block(CHARS_MENU);
         assert_eq!(pre_right.3, CHARS_CARD);
 
-        self.render_menu(input, left);
+        self.render_menu(left);
 
         let right = view.sub_view(pre_right.0, pre_right.1, pre_right.2, pre_right.3);
-        self.render_card(input, right);
+        self.render_card(right);
     }
 
-    pub fn render(&mut self, input: &Input) -> Grid<Cell> {
+    /// Draws the current state. The input is only shown, it does not change the world.
+    pub fn render(&self, input: &Input) -> Grid<Cell> {
         let mut grid = Grid::new(LINES_GRID, CHARS_GRID, Cell::new());
         let mut view = grid.view();
 
         let mut top_view = view.sub_view(0, 0, LINES_MAIN_FRAME, CHARS_GRID);
         top_view.fill_foreground(self.cards.get_card(self.cards.selected).color());
 
-        if 2 <= self.cards.available_cards().len() {
-            self.render_main_navigation(input, top_view);
+        if let Some(outcome) = self.climate_outcome() {
+            self.render_end_screen(outcome, top_view);
+        } else if 2 <= self.cards.available_cards().len() {
+            self.render_main_navigation(top_view);
         } else {
-            self.render_main_card(input, top_view)
+            self.render_main_card(top_view)
         }
 
         let bottom_view: MutGridView<'_, Cell> =
@@ -76,32 +79,19 @@ impl World {
         grid
     }
 
-    fn render_main_card(&mut self, input: &Input, mut view: MutGridView<'_, Cell>) {
+    fn render_main_card(&self, mut view: MutGridView<'_, Cell>) {
         assert_eq!(view.height(), LINES_MAIN_FRAME);
         assert_eq!(view.width(), CHARS_GRID);
 
         let inner = view.block();
-        self.render_card(input, inner);
+        self.render_card(inner);
     }
 
-    fn render_menu(&mut self, input: &Input, mut view: MutGridView<'_, Cell>) {
+    fn render_menu(&self, mut view: MutGridView<'_, Cell>) {
         assert_eq!(view.height(), LINES_MAIN_FRAME_CONTENT);
         assert_eq!(view.width(), CHARS_MENU);
 
         let available_cards = self.cards.available_cards();
-        let mut current_pos = available_cards
-            .iter()
-            .position(|c| c == &self.cards.selected)
-            .unwrap();
-
-        match input.event {
-            Some(Event::Key(Key::Down)) => {
-                current_pos = (available_cards.len() - 1).min(current_pos + 1)
-            }
-            Some(Event::Key(Key::Up)) => current_pos = current_pos.saturating_sub(1),
-            _ => {}
-        }
-        self.cards.selected = available_cards[current_pos];
 
         for (line, card) in available_cards.into_iter().enumerate() {
             let char_id = if card == self.cards.selected { 1 } else { 0 };
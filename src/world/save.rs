@@ -0,0 +1,193 @@
+use std::{error::Error, fmt::Display, fs, io, path::Path, time::SystemTime};
+
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::replay::REPLAY_FORMAT_VERSION;
+
+use super::World;
+
+/// Version of the on-disk save format.
+/// Bump whenever the serialized layout of [`World`] changes, which also retires older replays.
+pub const SAVE_FORMAT_VERSION: u32 = 16;
+/// Oldest save format version that can still be loaded.
+/// Version 3 embeds the research tree, older versions referred to hard-coded projects.
+const MINIMAL_SUPPORTED_SAVE_FORMAT_VERSION: u32 = 3;
+
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+#[derive(Serialize)]
+struct SaveFileRef<'a> {
+    version: u32,
+    saved_at: Option<SystemTime>,
+    world: &'a World,
+}
+
+#[derive(Deserialize)]
+struct SaveFile {
+    #[serde(default)]
+    saved_at: Option<SystemTime>,
+    world: World,
+}
+
+pub struct LoadedSave {
+    pub world: World,
+    /// Wall-clock time of the save, if the save format records it.
+    pub saved_at: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Serialize(ron::Error),
+    Deserialize(ron::error::SpannedError),
+    UnsupportedVersion(u32),
+    UnsupportedReplay { version: u32, save_version: u32 },
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "cannot access save file: {error}"),
+            SaveError::Serialize(error) => write!(f, "cannot write save: {error}"),
+            SaveError::Deserialize(error) => write!(f, "corrupt save: {error}"),
+            SaveError::UnsupportedVersion(version) => write!(
+                f,
+                "save has format version {version}, but only versions {MINIMAL_SUPPORTED_SAVE_FORMAT_VERSION} to {SAVE_FORMAT_VERSION} are supported"
+            ),
+            SaveError::UnsupportedReplay {
+                version,
+                save_version,
+            } => write!(
+                f,
+                "replay has format version {version} with saves of version {save_version}, but only format version {REPLAY_FORMAT_VERSION} with saves of version {SAVE_FORMAT_VERSION} can be played"
+            ),
+        }
+    }
+}
+
+impl Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<ron::Error> for SaveError {
+    fn from(value: ron::Error) -> Self {
+        Self::Serialize(value)
+    }
+}
+
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Deserialize(value)
+    }
+}
+
+impl World {
+    pub fn to_save_string(&self, saved_at: SystemTime) -> Result<String, SaveError> {
+        let save_file = SaveFileRef {
+            version: SAVE_FORMAT_VERSION,
+            saved_at: Some(saved_at),
+            world: self,
+        };
+        Ok(ron::ser::to_string_pretty(
+            &save_file,
+            PrettyConfig::default(),
+        )?)
+    }
+
+    pub fn from_save_string(save: &str) -> Result<LoadedSave, SaveError> {
+        let header: SaveHeader = ron::from_str(save)?;
+        if !(MINIMAL_SUPPORTED_SAVE_FORMAT_VERSION..=SAVE_FORMAT_VERSION).contains(&header.version)
+        {
+            return Err(SaveError::UnsupportedVersion(header.version));
+        }
+
+        let save_file: SaveFile = ron::from_str(save)?;
+        Ok(LoadedSave {
+            world: save_file.world,
+            saved_at: save_file.saved_at,
+        })
+    }
+
+    /// Writes to a temporary file first, so an interrupted save never destroys the previous one.
+    pub fn save(&self, path: &Path) -> Result<(), SaveError> {
+        let save = self.to_save_string(SystemTime::now())?;
+        let temporary_path = path.with_extension("tmp");
+        fs::write(&temporary_path, save)?;
+        fs::rename(temporary_path, path)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<LoadedSave, SaveError> {
+        Self::from_save_string(&fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration as TimeDuration, SystemTime};
+
+    use crate::{duration::Duration, world::action::Action};
+
+    use super::{SaveError, World, SAVE_FORMAT_VERSION};
+
+    #[test]
+    fn round_trip() {
+        let mut world = World::new();
+        for _ in 0..10 {
+            world.apply_action(Action::HandoutFlyer);
+        }
+        for _ in 0..6 {
+            world.apply_action(Action::PrintFlyer);
+        }
+        world.simulate(Duration::from_seconds(1234) + Duration::MICROSECOND);
+
+        let saved_at = SystemTime::UNIX_EPOCH + TimeDuration::from_secs(1_700_000_000);
+        let save = world.to_save_string(saved_at).unwrap();
+        let loaded = World::from_save_string(&save).unwrap();
+
+        assert_eq!(loaded.saved_at, Some(saved_at));
+        assert_eq!(loaded.world.get_total_ticks(), world.get_total_ticks());
+        assert_eq!(loaded.world.to_save_string(saved_at).unwrap(), save);
+    }
+
+    #[test]
+    fn loads_oldest_supported_version() {
+        let save = include_str!("fixtures/save_v3.ron");
+        let loaded = World::from_save_string(save).unwrap();
+        assert_eq!(
+            loaded.world.get_total_ticks(),
+            Duration::from_seconds(1234) + Duration::MICROSECOND
+        );
+        assert!(loaded.world.describe().contains(&"Flyer: 1".to_owned()));
+
+        let saved_at = loaded.saved_at.unwrap();
+        let save = loaded.world.to_save_string(saved_at).unwrap();
+        let reloaded = World::from_save_string(&save).unwrap();
+        assert_eq!(reloaded.world.to_save_string(saved_at).unwrap(), save);
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let save = World::new()
+            .to_save_string(SystemTime::now())
+            .unwrap()
+            .replacen(
+                &format!("version: {SAVE_FORMAT_VERSION}"),
+                &format!("version: {}", SAVE_FORMAT_VERSION + 1),
+                1,
+            );
+
+        assert!(matches!(
+            World::from_save_string(&save),
+            Err(SaveError::UnsupportedVersion(version)) if version == SAVE_FORMAT_VERSION + 1
+        ));
+    }
+}